@@ -0,0 +1,60 @@
+//! Golden answers for a day's real `input`, locked in `answers.toml`:
+//!
+//! ```toml
+//! part1 = "54697"
+//! part2 = "54885"
+//! ```
+//!
+//! Running the tests with `AOC_LOCK_ANSWERS=1` records the current answers of
+//! the parts missing from the file.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use anyhow::{Context, Result};
+
+const LOCK_VAR: &str = "AOC_LOCK_ANSWERS";
+
+/// Both parts' tests run concurrently and would race on `answers.toml`.
+static LOCK_FILE: Mutex<()> = Mutex::new(());
+
+/// Locked answers of the day crate in `day_dir`, keyed by `part1`/`part2`.
+pub fn load(day_dir: impl AsRef<Path>) -> Result<BTreeMap<String, String>> {
+    match fs::read_to_string(day_dir.as_ref().join("answers.toml")) {
+        Ok(content) => toml::from_str(&content).context("Invalid answers.toml"),
+        Err(_) => Ok(BTreeMap::new()),
+    }
+}
+
+fn lock(day_dir: &Path, part: u32, answer: &str) -> Result<()> {
+    let _guard = LOCK_FILE.lock().unwrap();
+    let mut answers = load(day_dir)?;
+    answers.insert(format!("part{}", part), answer.to_string());
+    fs::write(day_dir.join("answers.toml"), toml::to_string(&answers)?)?;
+    Ok(())
+}
+
+/// Check the registered solver of `day` against the locked answer of `part`
+/// for the `input` found in `day_dir`, recording the answer instead when
+/// `AOC_LOCK_ANSWERS` is set. A day without an input (or with the empty
+/// placeholder of `aoc new`) or a locked answer fails: nothing would be
+/// checked. Days whose input isn't committed `#[ignore]` their tests.
+pub fn check(day_dir: impl AsRef<Path>, day: u32, part: u32) {
+    let day_dir = day_dir.as_ref();
+    let input = fs::read_to_string(day_dir.join("input")).unwrap_or_default();
+    if input.is_empty() {
+        panic!("No input in {}: add it, or ignore the test until it is available", day_dir.display());
+    }
+    let answers = load(day_dir).expect("Failed to load answers");
+    let solve = || crate::registry::solve(day, part, &input).expect("Failed to run solver");
+    match answers.get(&format!("part{}", part)) {
+        Some(locked) => assert_eq!(&solve(), locked, "Part {} answer changed", part),
+        None if std::env::var_os(LOCK_VAR).is_some() => {
+            let answer = solve();
+            lock(day_dir, part, &answer).expect("Failed to lock answer");
+            eprintln!("Locked part {} answer: {}", part, answer);
+        },
+        None => panic!("No locked answer for part {} in {}: set {} to record it", part, day_dir.display(), LOCK_VAR),
+    }
+}
@@ -0,0 +1,97 @@
+//! Errors pointing into an input, rendered with the offending line, a caret
+//! under the span at fault and a hint:
+//!
+//! ```text
+//! invalid number "x": invalid digit found in string
+//!  --> day-4/input:2:11
+//!   |
+//! 2 | Card 2: 1 x | 3
+//!   |           ^
+//!   = hint: expected a u32
+//! ```
+//!
+//! Parsers build them from `parse::Span`s, which don't know the file they
+//! come from: callers name it with `in_file`.
+
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Name of the input, `None` until given with `in_file`
+    pub file: Option<String>,
+    /// Line and column of the start of the span, counting from 1
+    pub line: u32,
+    pub column: u32,
+    /// Bytes of the input at fault
+    pub span: Range<usize>,
+    pub message: String,
+    pub hint: Option<String>,
+    /// The line holding the start of the span
+    pub excerpt: String,
+}
+
+impl Diagnostic {
+    /// A diagnostic for `input[span]`. The excerpt leaves out a byte order
+    /// mark and the `\r` of CRLF line endings.
+    pub fn new(input: &str, span: Range<usize>, message: impl fmt::Display) -> Self {
+        let before = &input[..span.start];
+        let line_start = match before.rfind('\n') {
+            Some(at) => at + 1,
+            None if before.starts_with('\u{feff}') => '\u{feff}'.len_utf8(),
+            None => 0,
+        };
+        let line_end = input[span.start..].find('\n').map_or(input.len(), |at| span.start + at);
+        let excerpt = &input[line_start..line_end];
+        Self {
+            file: None,
+            line: before.matches('\n').count() as u32 + 1,
+            column: before[line_start..].chars().count() as u32 + 1,
+            span,
+            message: message.to_string(),
+            hint: None,
+            excerpt: excerpt.strip_suffix('\r').unwrap_or(excerpt).to_string(),
+        }
+    }
+
+    pub fn with_hint(mut self, hint: impl fmt::Display) -> Self {
+        self.hint = Some(hint.to_string());
+        self
+    }
+
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let file = self.file.as_deref().unwrap_or("<input>");
+        let skipped: String = self.excerpt.chars().take(self.column as usize - 1)
+                                          .map(|c| match c { '\t' => '\t', _ => ' ' })
+                                          .collect();
+        let rest = &self.excerpt[self.excerpt.char_indices().nth(self.column as usize - 1).map_or(self.excerpt.len(), |(at, _)| at)..];
+        let width = rest.char_indices().take_while(|&(at, _)| at < self.span.len()).count().max(1);
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, file, self.line, self.column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.excerpt)?;
+        write!(f, "{} | {}{}", gutter, skipped, "^".repeat(width))?;
+        if let Some(hint) = &self.hint {
+            write!(f, "\n{} = hint: {}", gutter, hint)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// `err` with the file of its diagnostic, if it is one, set to `file`.
+pub fn in_file(err: anyhow::Error, file: &str) -> anyhow::Error {
+    match err.downcast::<Diagnostic>() {
+        Ok(diagnostic) => diagnostic.with_file(file).into(),
+        Err(err) => err,
+    }
+}
@@ -0,0 +1,141 @@
+//! Worked examples extracted from a day's `puzzle.md`.
+//!
+//! Each part's example input is the first fenced code block of that part
+//! (part two falls back to part one's block) and its expected answer is the
+//! last emphasised code span (`` `*42*` ``) of the part. When the text does
+//! not follow that pattern, an `examples.toml` next to `puzzle.md` overrides
+//! the extraction per part:
+//!
+//! ```toml
+//! [part2]
+//! block = 0          # index of the code block in the whole puzzle.md
+//! input = "..."      # or the example input itself
+//! answer = "46"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub part: u32,
+    pub input: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Override {
+    block: Option<usize>,
+    input: Option<String>,
+    answer: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct Section {
+    blocks: Vec<usize>,
+    answer: Option<String>,
+}
+
+/// Fenced code blocks of the whole document, and per part the indices of the
+/// blocks it contains and its expected answer.
+fn scan(puzzle: &str) -> (Vec<String>, Vec<Section>) {
+    let mut blocks = Vec::new();
+    let mut sections = vec![Section::default()];
+    let mut block: Option<String> = None;
+    for line in puzzle.lines() {
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some(content) => {
+                    sections.last_mut().unwrap().blocks.push(blocks.len());
+                    blocks.push(content);
+                },
+                None => block = Some(String::new()),
+            }
+            continue;
+        }
+        if let Some(content) = block.as_mut() {
+            content.push_str(line);
+            content.push('\n');
+            continue;
+        }
+        if line.contains("--- Part Two ---") {
+            sections.push(Section::default());
+            continue;
+        }
+        let mut rest = line;
+        while let Some(start) = rest.find("`*") {
+            rest = &rest[start+2..];
+            let Some(len) = rest.find("*`") else { break };
+            let answer = &rest[..len];
+            if !answer.is_empty() && !answer.contains(|c: char| c == '`' || c.is_whitespace()) {
+                sections.last_mut().unwrap().answer = Some(answer.to_string());
+                rest = &rest[len+2..];
+            }
+        }
+    }
+    (blocks, sections)
+}
+
+/// Code blocks keep the blank line AoC leaves before the closing fence; trim
+/// it so the example looks like a real input file.
+fn normalise(input: &str) -> String {
+    format!("{}\n", input.trim_end_matches('\n'))
+}
+
+/// Extract the examples of `puzzle` (the contents of `puzzle.md`), applying
+/// the `overrides` parsed from `examples.toml`.
+fn extract(puzzle: &str, overrides: &BTreeMap<String, Override>) -> Result<Vec<Example>> {
+    let (blocks, sections) = scan(puzzle);
+    let mut examples = Vec::new();
+    for part in 1..=2u32 {
+        let section = sections.get(part as usize - 1);
+        let part_override = overrides.get(&format!("part{}", part));
+        if section.is_none() && part_override.is_none() {
+            continue;
+        }
+        let block = part_override.and_then(|o| o.block)
+                                 .or(section.and_then(|s| s.blocks.first().copied()))
+                                 .or(sections[0].blocks.first().copied());
+        let input = match (part_override.and_then(|o| o.input.clone()), block) {
+            (Some(input), _) => input,
+            (None, Some(idx)) => blocks.get(idx)
+                                       .ok_or(anyhow!("Part {}: no code block {}", part, idx))?
+                                       .clone(),
+            (None, None) => continue,
+        };
+        let answer = part_override.and_then(|o| o.answer.clone())
+                                  .or(section.and_then(|s| s.answer.clone()));
+        if let Some(answer) = answer {
+            examples.push(Example { part, input: normalise(&input), answer });
+        }
+    }
+    Ok(examples)
+}
+
+/// Load the examples of the day crate in `day_dir`, from its `puzzle.md` and
+/// optional `examples.toml`. Either file may be missing.
+pub fn load(day_dir: impl AsRef<Path>) -> Result<Vec<Example>> {
+    let day_dir = day_dir.as_ref();
+    let puzzle = fs::read_to_string(day_dir.join("puzzle.md")).unwrap_or_default();
+    let overrides: BTreeMap<String, Override> = match fs::read_to_string(day_dir.join("examples.toml")) {
+        Ok(content) => toml::from_str(&content).context("Invalid examples.toml")?,
+        Err(_) => BTreeMap::new(),
+    };
+    extract(&puzzle, &overrides)
+}
+
+/// Check the registered solver of `day` against the example of `part` found
+/// in `day_dir`. A part without an example fails: nothing would be checked.
+pub fn check(day_dir: impl AsRef<Path>, day: u32, part: u32) {
+    let day_dir = day_dir.as_ref();
+    let examples = load(day_dir).expect("Failed to load examples");
+    let Some(example) = examples.iter().find(|example| example.part == part) else {
+        panic!("No example for part {} in {}: add its input and answer to puzzle.md or examples.toml", part, day_dir.display());
+    };
+    let answer = crate::registry::solve(day, part, &example.input).expect("Failed to run solver");
+    assert_eq!(answer, example.answer, "Part {} example:\n{}", part, example.input);
+}
@@ -0,0 +1,228 @@
+//! Puzzle inputs: the `input` file of a day directory when present, else the
+//! on-disk cache, else downloaded from the website and cached.
+//!
+//! Downloading needs the `online` feature and the `session` cookie of a
+//! logged in browser, read from `AOC_SESSION` or from
+//! `$XDG_CONFIG_HOME/aoc/session` (`~/.config/aoc/session`).
+//! The cache lives in `AOC_CACHE_DIR`, else `$XDG_CACHE_HOME/aoc` (`~/.cache/aoc`).
+//!
+//! `InputArgs` lets a binary take its inputs from the command line instead.
+
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context, Result};
+#[cfg(feature = "online")]
+use anyhow::anyhow;
+use clap::Args;
+use crate::diagnostic;
+
+pub const YEAR: u32 = 2023;
+const BASE_URL: &str = "https://adventofcode.com";
+#[cfg(feature = "online")]
+const USER_AGENT: &str = "github.com/crisbour/advent-of-code input provider";
+
+fn home_subdir(xdg_var: &str, fallback: &str) -> Option<PathBuf> {
+    env::var_os(xdg_var).map(PathBuf::from)
+                        .or(env::var_os("HOME").map(|home| PathBuf::from(home).join(fallback)))
+}
+
+/// Session token from `AOC_SESSION` or the config file, if any.
+pub fn session_from_env() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session.trim().to_string());
+    }
+    let path = home_subdir("XDG_CONFIG_HOME", ".config")?.join("aoc").join("session");
+    fs::read_to_string(path).ok().map(|session| session.trim().to_string())
+}
+
+/// Cache directory from `AOC_CACHE_DIR` or the user cache directory.
+pub fn cache_dir_from_env() -> PathBuf {
+    env::var_os("AOC_CACHE_DIR").map(PathBuf::from)
+                                .or(home_subdir("XDG_CACHE_HOME", ".cache").map(|dir| dir.join("aoc")))
+                                .unwrap_or(PathBuf::from(".aoc-cache"))
+}
+
+#[derive(Debug, Clone)]
+pub struct Provider {
+    cache_dir: PathBuf,
+    base_url: String,
+    session: Option<String>,
+    year: u32,
+    local_dir: Option<PathBuf>,
+}
+
+impl Provider {
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+            base_url: BASE_URL.to_string(),
+            session: None,
+            year: YEAR,
+            local_dir: None,
+        }
+    }
+
+    /// Provider configured from the environment, see the module documentation.
+    pub fn from_env() -> Self {
+        let provider = Self::new(cache_dir_from_env());
+        match session_from_env() {
+            Some(session) => provider.with_session(session),
+            None => provider,
+        }
+    }
+
+    /// Server to download from, `https://adventofcode.com` by default.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+
+    pub fn with_year(mut self, year: u32) -> Self {
+        self.year = year;
+        self
+    }
+
+    /// Look for an `input` file in `dir` before the cache.
+    pub fn with_local_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.local_dir = Some(dir.into());
+        self
+    }
+
+    /// Where the input of `day` is cached.
+    pub fn cache_path(&self, day: u32) -> PathBuf {
+        self.cache_dir.join(self.year.to_string()).join(format!("day-{}.txt", day))
+    }
+
+    /// Input of `day`, downloading it if it isn't available locally. An empty
+    /// local `input`, as left by `aoc new`, counts as missing.
+    pub fn input(&self, day: u32) -> Result<String> {
+        if let Some(dir) = &self.local_dir {
+            let path = dir.join("input");
+            if path.exists() {
+                let input = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+                if !input.is_empty() {
+                    return Ok(input);
+                }
+            }
+        }
+        let cache_path = self.cache_path(day);
+        if cache_path.exists() {
+            return fs::read_to_string(&cache_path).with_context(|| format!("Failed to read {}", cache_path.display()));
+        }
+        let input = self.fetch(day)?;
+        store(&cache_path, &input)?;
+        Ok(input)
+    }
+
+    #[cfg(not(feature = "online"))]
+    fn fetch(&self, day: u32) -> Result<String> {
+        bail!("Input of day {} isn't cached, and downloading it needs the `online` feature: fetch it with `aoc run --day {}`", day, day)
+    }
+
+    #[cfg(feature = "online")]
+    fn fetch(&self, day: u32) -> Result<String> {
+        let session = self.session.as_ref()
+                          .ok_or(anyhow!("Input of day {} isn't cached and no session is set (AOC_SESSION)", day))?;
+        let url = format!("{}/{}/day/{}/input", self.base_url, self.year, day);
+        let response = ureq::get(&url).set("Cookie", &format!("session={}", session))
+                                      .set("User-Agent", USER_AGENT)
+                                      .call();
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                bail!("Fetching {} failed with {}: {}", url, status, body.trim())
+            },
+            Err(err) => Err(err).with_context(|| format!("Fetching {} failed", url)),
+        }
+    }
+}
+
+/// Write through a temporary file so an interrupted download never leaves a
+/// truncated input in the cache.
+fn store(path: &Path, content: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Input of `day` for a day binary: the `input` file of the current directory,
+/// else the cache, else downloaded.
+pub fn load(day: u32) -> Result<String> {
+    Provider::from_env().with_local_dir(".").input(day)
+}
+
+/// Command line options choosing the puzzle inputs, to be
+/// `#[command(flatten)]`-ed into a `Parser`.
+#[derive(Args, Debug, Clone, Default)]
+pub struct InputArgs {
+    /// Input files solved one after the other, `-` for stdin; quoted glob
+    /// patterns are expanded. Defaults to the day's own input
+    #[arg(short, long = "input", value_name = "PATH", num_args = 1..)]
+    pub inputs: Vec<String>,
+}
+
+/// A puzzle input and where it was read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub name: String,
+    pub text: String,
+}
+
+impl Input {
+    /// Parse the text with `parser`, naming the input in its diagnostics.
+    pub fn parse<T>(&self, parser: impl FnOnce(&str) -> Result<T>) -> Result<T> {
+        parser(&self.text).map_err(|err| diagnostic::in_file(err, &self.name))
+    }
+}
+
+fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+impl InputArgs {
+    /// Whether inputs were given on the command line.
+    pub fn explicit(&self) -> bool {
+        !self.inputs.is_empty()
+    }
+
+    /// Read the inputs given on the command line, in order, or the one
+    /// returned by `default` named `input`.
+    pub fn load(&self, default: impl FnOnce() -> Result<String>) -> Result<Vec<Input>> {
+        if !self.explicit() {
+            return Ok(vec![Input { name: "input".to_string(), text: default()? }]);
+        }
+        let mut inputs = Vec::new();
+        for path in &self.inputs {
+            if path == "-" {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text).context("Failed to read stdin")?;
+                inputs.push(Input { name: path.clone(), text });
+            } else if is_glob(path) {
+                let matches: Vec<PathBuf> = glob::glob(path)?.collect::<Result<_, _>>()?;
+                if matches.is_empty() {
+                    bail!("No file matches {}", path);
+                }
+                for path in matches {
+                    let text = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+                    inputs.push(Input { name: path.display().to_string(), text });
+                }
+            } else {
+                let text = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+                inputs.push(Input { name: path.clone(), text });
+            }
+        }
+        Ok(inputs)
+    }
+}
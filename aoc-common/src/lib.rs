@@ -0,0 +1,73 @@
+//! Helpers shared by every day binary.
+//!
+//! What only the `aoc` runner and the tests need sits behind features, so the
+//! day binaries don't build it: `online` (downloads and `submit`),
+//! `chrome-trace` (`--trace-file`), `checks` (`answers` and `examples`) and
+//! `visualise` (exporting the recorded frames).
+
+pub mod alloc;
+#[cfg(feature = "checks")]
+pub mod answers;
+pub mod diagnostic;
+#[cfg(feature = "checks")]
+pub mod examples;
+pub mod input;
+pub mod normalise;
+pub mod output;
+pub mod parse;
+pub mod registry;
+#[cfg(feature = "online")]
+pub mod submit;
+pub mod trace;
+pub mod visualise;
+
+pub use aoc_macros::{aoc, aoc_parser};
+#[doc(hidden)]
+pub use ::anyhow;
+#[doc(hidden)]
+pub use ::inventory;
+#[doc(hidden)]
+pub use ::serde_json;
+#[doc(hidden)]
+pub use ::tracing;
+
+use clap::{Args, ValueEnum};
+
+/// Puzzle part(s) to solve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    /// Only the first part
+    #[value(name = "1")]
+    One,
+    /// Only the second part
+    #[value(name = "2")]
+    Two,
+    /// Both parts, one after the other
+    All,
+}
+
+impl Part {
+    /// Part numbers selected, in the order they should be solved.
+    pub fn numbers(self) -> Vec<u32> {
+        match self {
+            Part::One => vec![1],
+            Part::Two => vec![2],
+            Part::All => vec![1, 2],
+        }
+    }
+}
+
+/// Command line options common to every day, to be `#[command(flatten)]`-ed
+/// into the day's own `Parser`.
+#[derive(Args, Debug)]
+pub struct PartArgs {
+    /// Puzzle part to solve
+    #[arg(short, long, value_enum, default_value = "all")]
+    pub part: Part,
+}
+
+impl PartArgs {
+    pub fn parts(&self) -> Vec<u32> {
+        self.part.numbers()
+    }
+}
@@ -0,0 +1,147 @@
+//! Input clean-up run by `#[aoc_parser]` before every parser, so inputs
+//! edited on Windows or pasted with stray blank lines parse like the
+//! originals: Unix line endings, no byte order mark, no trailing whitespace,
+//! single blank lines between blocks and exactly one final newline. Blank
+//! lines within the input are kept, so the parsers of single block inputs
+//! report them instead of dropping what follows.
+
+use std::borrow::Cow;
+use std::fmt;
+use tracing::warn;
+use crate::diagnostic::Diagnostic;
+
+/// A difference between an input and its normalised form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fix {
+    ByteOrderMark,
+    CrlfLineEndings,
+    TrailingWhitespace,
+    LeadingBlankLines,
+    RepeatedBlankLines,
+    TrailingBlankLines,
+    MissingFinalNewline,
+}
+
+impl fmt::Display for Fix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Fix::ByteOrderMark       => "byte order mark",
+            Fix::CrlfLineEndings     => "CRLF line endings",
+            Fix::TrailingWhitespace  => "trailing whitespace",
+            Fix::LeadingBlankLines   => "leading blank lines",
+            Fix::RepeatedBlankLines  => "repeated blank lines",
+            Fix::TrailingBlankLines  => "trailing blank lines",
+            Fix::MissingFinalNewline => "missing final newline",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// An input in normal form, with what was fixed and where its lines come
+/// from in the original.
+#[derive(Debug, Clone)]
+pub struct Normalised<'a> {
+    /// The normalised input, borrowed when it already was
+    pub text: Cow<'a, str>,
+    pub fixes: Vec<Fix>,
+    original: &'a str,
+    /// Per line of `text`: its offset there, its offset in `original` and
+    /// its length
+    lines: Vec<(usize, usize, usize)>,
+}
+
+impl Normalised<'_> {
+    /// Offset in the original input of `offset` in the normalised one.
+    pub fn original_offset(&self, offset: usize) -> usize {
+        let at = self.lines.partition_point(|&(start, _, _)| start <= offset);
+        match at.checked_sub(1).map(|at| self.lines[at]) {
+            Some((start, original, len)) => (original + (offset - start).min(len)).min(self.original.len()),
+            None => self.original.len() - self.original.trim_start_matches('\u{feff}').len(),
+        }
+    }
+
+    /// `err` with its diagnostic, if it is one, pointing into the original
+    /// input rather than the normalised one, which the user never saw.
+    pub fn locate(&self, err: anyhow::Error) -> anyhow::Error {
+        match err.downcast::<Diagnostic>() {
+            Ok(diagnostic) => {
+                let start = self.original_offset(diagnostic.span.start);
+                let end = self.original_offset(diagnostic.span.end).max(start);
+                Diagnostic { file: diagnostic.file, hint: diagnostic.hint, ..Diagnostic::new(self.original, start..end, diagnostic.message) }.into()
+            },
+            Err(err) => err,
+        }
+    }
+}
+
+/// `input` in normal form and what was fixed.
+pub fn normalise(input: &str) -> Normalised<'_> {
+    let mut fixes = Vec::new();
+    let mut fix = |fix: Fix| if !fixes.contains(&fix) { fixes.push(fix) };
+    let text = match input.strip_prefix('\u{feff}') {
+        Some(text) => { fix(Fix::ByteOrderMark); text },
+        None => input,
+    };
+    let offset = |line: &str| line.as_ptr() as usize - input.as_ptr() as usize;
+    if text.is_empty() {
+        return Normalised { text: Cow::Borrowed(text), fixes, original: input, lines: Vec::new() };
+    }
+    if !text.ends_with('\n') {
+        fix(Fix::MissingFinalNewline);
+    }
+    // Kept lines and their offset in `input`
+    let mut lines: Vec<(&str, usize)> = Vec::new();
+    let mut blanks = None;
+    for line in text.strip_suffix('\n').unwrap_or(text).split('\n') {
+        let unix = match line.strip_suffix('\r') {
+            Some(unix) => { fix(Fix::CrlfLineEndings); unix },
+            None => line,
+        };
+        let trimmed = unix.trim_end();
+        if trimmed.len() != unix.len() {
+            fix(Fix::TrailingWhitespace);
+        }
+        if trimmed.is_empty() {
+            blanks = match blanks {
+                None => Some((offset(line), 1)),
+                Some((first, count)) => Some((first, count + 1)),
+            };
+            continue;
+        }
+        match (lines.is_empty(), blanks) {
+            (_, None) => {},
+            (true, Some(_)) => fix(Fix::LeadingBlankLines),
+            (false, Some((first, 1))) => lines.push(("", first)),
+            (false, Some((first, _))) => { fix(Fix::RepeatedBlankLines); lines.push(("", first)); },
+        }
+        blanks = None;
+        lines.push((trimmed, offset(line)));
+    }
+    if blanks.is_some() {
+        fix(match lines.is_empty() {
+            true => Fix::LeadingBlankLines,
+            false => Fix::TrailingBlankLines,
+        });
+    }
+    let map = lines.iter().scan(0, |start, &(line, original)| {
+                              *start += line.len() + 1;
+                              Some((*start - line.len() - 1, original, line.len()))
+                          })
+                          .collect();
+    let text = match (fixes.is_empty(), lines.is_empty()) {
+        (true, _) => Cow::Borrowed(input),
+        (false, true) => Cow::Owned(String::new()),
+        (false, false) => Cow::Owned(lines.iter().map(|(line, _)| *line).collect::<Vec<_>>().join("\n") + "\n"),
+    };
+    Normalised { text, fixes, original: input, lines: map }
+}
+
+/// `normalise`, warning about what was fixed.
+pub fn normalise_logged(input: &str) -> Normalised<'_> {
+    let normalised = normalise(input);
+    if !normalised.fixes.is_empty() {
+        let fixes: Vec<String> = normalised.fixes.iter().map(|fix| fix.to_string()).collect();
+        warn!("Normalised input: {}", fixes.join(", "));
+    }
+    normalised
+}
@@ -0,0 +1,172 @@
+//! One record per solved part, printed as text for people or as JSON lines
+//! or CSV for scripts and dashboards.
+
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::alloc::{self, AllocStats};
+use crate::{input, registry};
+
+/// How results are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// `Day 5 part 1: 35`, followed by the timings and allocations when
+    /// they were counted
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Comma separated values after a header line
+    Csv,
+}
+
+const CSV_HEADER: &str = "year,day,part,answer,expected,parse_ns,solve_ns,input_hash,\
+parse_allocs,parse_bytes,parse_peak_bytes,solve_allocs,solve_bytes,solve_peak_bytes,input";
+
+/// Answer of one part with how it was obtained.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    /// Expected answer, for the examples of `puzzle.md`
+    pub expected: Option<String>,
+    /// Time to parse the input, shared by the parts solved from it
+    pub parse_ns: u64,
+    pub solve_ns: u64,
+    /// SHA-256 of the input, in hex
+    pub input_hash: String,
+    /// Allocations of the parse, when the runner counts them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_alloc: Option<AllocStats>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solve_alloc: Option<AllocStats>,
+    /// Name of the input, when given on the command line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+/// Hex SHA-256 of `input`.
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes()).iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{:02x}", byte);
+        hex
+    })
+}
+
+/// Parse `input` once and solve `parts` of `day` with the registered solvers.
+pub fn solve(day: u32, parts: &[u32], input: &str) -> Result<Vec<Record>> {
+    records(day, parts, input, || registry::parse(day, input))
+}
+
+/// Like `solve`, `input` being a JSON model as printed by `registry::dump`;
+/// loading it counts as parsing.
+pub fn solve_json(day: u32, parts: &[u32], input: &str) -> Result<Vec<Record>> {
+    records(day, parts, input, || registry::load(day, serde_json::from_str(input)?))
+}
+
+fn records(day: u32, parts: &[u32], input: &str, parse: impl FnOnce() -> Result<registry::Model>) -> Result<Vec<Record>> {
+    let start = Instant::now();
+    let (model, parse_alloc) = alloc::measure(parse);
+    let parse_ns = nanos(start.elapsed());
+    let model = model?;
+    let input_hash = input_hash(input);
+    parts.iter().map(|&part| {
+        let start = Instant::now();
+        let (answer, solve_alloc) = alloc::measure(|| registry::solve_model(day, part, &model));
+        let solve_ns = nanos(start.elapsed());
+        Ok(Record {
+            year: input::YEAR,
+            day,
+            part,
+            answer: answer?,
+            expected: None,
+            parse_ns,
+            solve_ns,
+            input_hash: input_hash.clone(),
+            parse_alloc,
+            solve_alloc,
+            input: None,
+        })
+    }).collect()
+}
+
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+fn bytes(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1048575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1048576.0),
+    }
+}
+
+fn costs(ns: u64, alloc: AllocStats) -> String {
+    format!("{:.3} ms, {} allocs, {}, peak {}", ns as f64 / 1e6, alloc.allocs, bytes(alloc.bytes), bytes(alloc.peak_bytes))
+}
+
+fn alloc_fields(alloc: Option<AllocStats>) -> [String; 3] {
+    match alloc {
+        Some(alloc) => [alloc.allocs.to_string(), alloc.bytes.to_string(), alloc.peak_bytes.to_string()],
+        None => Default::default(),
+    }
+}
+
+impl Format {
+    /// Line to print before the records, if any.
+    pub fn header(self) -> Option<&'static str> {
+        match self {
+            Format::Csv => Some(CSV_HEADER),
+            _ => None,
+        }
+    }
+}
+
+impl Record {
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Text => {
+                let mut line = format!("Day {} part {}", self.day, self.part);
+                if let Some(input) = &self.input {
+                    line += &format!(" ({})", input);
+                }
+                let line = match &self.expected {
+                    Some(expected) if *expected != self.answer => {
+                        format!("{} example: {} (expected {})", line, self.answer, expected)
+                    },
+                    Some(_) => format!("{} example: {}", line, self.answer),
+                    None => format!("{}: {}", line, self.answer),
+                };
+                match (self.parse_alloc, self.solve_alloc) {
+                    (Some(parse), Some(solve)) => {
+                        format!("{}  [parse {}; solve {}]", line, costs(self.parse_ns, parse), costs(self.solve_ns, solve))
+                    },
+                    _ => line,
+                }
+            },
+            Format::Json => serde_json::to_string(self).expect("Records always serialise"),
+            Format::Csv => {
+                let mut fields = vec![self.year.to_string(), self.day.to_string(), self.part.to_string(),
+                                      csv_field(&self.answer), csv_field(self.expected.as_deref().unwrap_or("")),
+                                      self.parse_ns.to_string(), self.solve_ns.to_string(), self.input_hash.clone()];
+                fields.extend(alloc_fields(self.parse_alloc));
+                fields.extend(alloc_fields(self.solve_alloc));
+                fields.push(csv_field(self.input.as_deref().unwrap_or("")));
+                fields.join(",")
+            },
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Small parsing toolkit shared by the day parsers.
+//!
+//! Combinators work on `Span`s, pieces of the input that remember where they
+//! are, so every error is a `Diagnostic` pointing at the text at fault:
+//!
+//! ```
+//! use aoc_common::parse::{ints, labelled, lines, pair, Span};
+//!
+//! let input = Span::new("Card 1: 41 48 | 83 86\n");
+//! for line in lines(input) {
+//!     let (id, numbers): (u32, _) = labelled(line, "Card")?;
+//!     let (winning, own) = pair(numbers, '|')?;
+//!     let (winning, own): (Vec<u32>, Vec<u32>) = (ints(winning)?, ints(own)?);
+//!     assert_eq!((id, winning, own), (1, vec![41, 48], vec![83, 86]));
+//! }
+//! # Ok::<(), aoc_common::diagnostic::Diagnostic>(())
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use crate::diagnostic::Diagnostic;
+
+/// A piece of the input, `input[start..end]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    input: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Span<'a> {
+    /// The whole of `input`.
+    pub fn new(input: &'a str) -> Self {
+        Self { input, start: 0, end: input.len() }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        &self.input[self.start..self.end]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Offset of the span in the input.
+    pub fn offset(&self) -> usize {
+        self.start
+    }
+
+    /// An error pointing at the span.
+    pub fn error(&self, message: impl fmt::Display) -> Diagnostic {
+        Diagnostic::new(self.input, self.start..self.end, message)
+    }
+
+    /// The part of the span at `range`, in bytes from its start.
+    pub fn slice(&self, range: std::ops::Range<usize>) -> Self {
+        assert!(self.as_str().is_char_boundary(range.start) && self.as_str().is_char_boundary(range.end));
+        Self { input: self.input, start: self.start + range.start, end: self.start + range.end }
+    }
+
+    pub fn trim(&self) -> Self {
+        let text = self.as_str();
+        let start = text.len() - text.trim_start().len();
+        self.slice(start..start + text.trim().len())
+    }
+
+    /// The spans before and after the first `sep`.
+    pub fn split_once(&self, sep: char) -> Option<(Self, Self)> {
+        let at = self.as_str().find(sep)?;
+        Some((self.slice(0..at), self.slice(at + sep.len_utf8()..self.end - self.start)))
+    }
+
+    /// The pieces between the occurrences of `sep`.
+    pub fn split(self, sep: &'a str) -> impl Iterator<Item = Span<'a>> + 'a {
+        self.as_str().split(sep).map(move |piece| {
+            let start = piece.as_ptr() as usize - self.as_str().as_ptr() as usize;
+            self.slice(start..start + piece.len())
+        })
+    }
+}
+
+/// Lines of `input`, without the empty piece after the final newline.
+pub fn lines<'a>(input: Span<'a>) -> impl Iterator<Item = Span<'a>> + 'a {
+    let input = match input.as_str().strip_suffix('\n') {
+        Some(text) => input.slice(0..text.len()),
+        None => input,
+    };
+    input.split("\n").filter(move |line| !(input.is_empty() && line.is_empty()))
+}
+
+/// Blocks of lines separated by a blank line.
+pub fn blocks<'a>(input: Span<'a>) -> impl Iterator<Item = Span<'a>> + 'a {
+    let input = match input.as_str().strip_suffix('\n') {
+        Some(text) => input.slice(0..text.len()),
+        None => input,
+    };
+    input.split("\n\n").filter(move |block| !(input.is_empty() && block.is_empty()))
+}
+
+/// The only block of `input`, empty for an empty input. A blank line followed
+/// by more content is an error rather than a silently dropped block.
+pub fn single_block(input: Span<'_>) -> Result<Span<'_>, Diagnostic> {
+    let mut found = blocks(input);
+    let block = found.next().unwrap_or(input);
+    match found.next().and_then(|extra| lines(extra).next()) {
+        Some(extra) => Err(extra.error("unexpected content after a blank line")
+                                .with_hint("the input is a single block of lines: remove the blank line")),
+        None => Ok(block),
+    }
+}
+
+/// Whitespace-separated words.
+pub fn words<'a>(input: Span<'a>) -> impl Iterator<Item = Span<'a>> + 'a {
+    input.split(" ").flat_map(|piece| piece.split("\t")).filter(|word| !word.is_empty())
+}
+
+/// The trimmed pieces between the occurrences of `sep`, e.g. `a; b; c`.
+pub fn separated<'a>(input: Span<'a>, sep: &'a str) -> impl Iterator<Item = Span<'a>> + 'a {
+    input.split(sep).map(|piece| piece.trim())
+}
+
+/// Exactly `N` whitespace-separated words.
+pub fn tokens<const N: usize>(input: Span<'_>) -> Result<[Span<'_>; N], Diagnostic> {
+    let found: Vec<Span> = words(input).collect();
+    found.try_into().map_err(|found: Vec<Span>| input.error(format!("expected {} words, found {}", N, found.len()))
+                                                      .with_hint("words are separated by spaces"))
+}
+
+/// The two trimmed sections of `a <sep> b`.
+pub fn pair(input: Span<'_>, sep: char) -> Result<(Span<'_>, Span<'_>), Diagnostic> {
+    let hint = format!("expected two sections separated by a single `{}`", sep);
+    let (left, right) = input.split_once(sep).ok_or_else(|| input.error(format!("expected `{}`", sep)).with_hint(&hint))?;
+    if let Some(at) = right.as_str().find(sep) {
+        return Err(right.slice(at..at + sep.len_utf8()).error(format!("unexpected second `{}`", sep)).with_hint(hint));
+    }
+    Ok((left.trim(), right.trim()))
+}
+
+/// The trimmed label and content of `label: content`.
+pub fn label(input: Span<'_>) -> Result<(Span<'_>, Span<'_>), Diagnostic> {
+    let (label, content) = input.split_once(':').ok_or_else(|| input.error("expected `<label>:`").with_hint("the line should start with a label followed by `:`"))?;
+    Ok((label.trim(), content.trim()))
+}
+
+/// The trimmed content of `<name>: content`.
+pub fn field<'a>(input: Span<'a>, name: &str) -> Result<Span<'a>, Diagnostic> {
+    match label(input)? {
+        (found, content) if found.as_str() == name => Ok(content),
+        (found, _) => Err(found.error(format!("expected `{}:`", name)).with_hint(format!("found `{}:` instead", found.as_str()))),
+    }
+}
+
+/// The id and trimmed content of `<name> <id>: content`, e.g. `Card 12: ...`.
+pub fn labelled<'a, T: FromStr>(input: Span<'a>, name: &str) -> Result<(T, Span<'a>), Diagnostic>
+    where T::Err: fmt::Display
+{
+    let (found, content) = label(input)?;
+    let id = found.as_str().strip_prefix(name).filter(|id| id.starts_with(char::is_whitespace))
+                           .ok_or_else(|| found.error(format!("expected `{} <id>:`", name))
+                                                    .with_hint(format!("each line should start like `{} 1:`", name)))?;
+    Ok((int(found.slice(name.len()..name.len() + id.len()).trim())?, content))
+}
+
+/// The number spanning the whole of `input`.
+pub fn int<T: FromStr>(input: Span) -> Result<T, Diagnostic>
+    where T::Err: fmt::Display
+{
+    input.as_str().parse().map_err(|err| input.error(format!("invalid number {:?}: {}", input.as_str(), err))
+                                              .with_hint(format!("expected a {}", std::any::type_name::<T>())))
+}
+
+/// Whitespace-separated numbers.
+pub fn ints<T: FromStr>(input: Span) -> Result<Vec<T>, Diagnostic>
+    where T::Err: fmt::Display
+{
+    words(input).map(int).collect()
+}
+
+/// Rows of a rectangular grid of characters.
+pub fn grid(input: Span<'_>) -> Result<Vec<Span<'_>>, Diagnostic> {
+    let rows: Vec<Span> = lines(input).collect();
+    let Some(width) = rows.first().map(|row| row.as_str().chars().count()) else {
+        return Err(input.error("expected a grid").with_hint("the input is empty"));
+    };
+    for row in &rows {
+        let found = row.as_str().chars().count();
+        if found != width {
+            return Err(row.error(format!("row is {} cells wide, expected {}", found, width))
+                          .with_hint("every row should be as wide as the first one"));
+        }
+    }
+    Ok(rows)
+}
@@ -0,0 +1,87 @@
+//! Solvers registered with the `#[aoc_parser]` and `#[aoc]` attributes.
+//!
+//! Models are serde types, so they can also be dumped to and loaded from
+//! JSON, e.g. to feed a hand-written model to the solvers.
+//!
+//! Registration happens at link time, so only the days linked into the
+//! current binary are visible: the day crates have to be actually used by
+//! it, e.g. with `use day_4 as _;`.
+
+use std::any::Any;
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Parsed input of a day, as returned by its parser.
+pub type Model = Box<dyn Any>;
+
+/// Input parser of a day, and the conversions of its model from and to JSON.
+pub struct Parser {
+    pub day: u32,
+    pub parse: fn(&str) -> Result<Model>,
+    pub dump: fn(&Model) -> Result<Value>,
+    pub load: fn(Value) -> Result<Model>,
+}
+
+/// Solver of one part of a day.
+pub struct Solver {
+    pub day: u32,
+    pub part: u32,
+    pub solve: fn(&Model) -> Result<String>,
+}
+
+inventory::collect!(Parser);
+inventory::collect!(Solver);
+
+pub fn parser(day: u32) -> Option<&'static Parser> {
+    inventory::iter::<Parser>().find(|parser| parser.day == day)
+}
+
+pub fn solver(day: u32, part: u32) -> Option<&'static Solver> {
+    inventory::iter::<Solver>().find(|solver| solver.day == day && solver.part == part)
+}
+
+/// Days with a registered parser, in order.
+pub fn days() -> Vec<u32> {
+    let mut days: Vec<u32> = inventory::iter::<Parser>().map(|parser| parser.day).collect();
+    days.sort();
+    days.dedup();
+    days
+}
+
+/// Parts of `day` with a registered solver, in order.
+pub fn parts(day: u32) -> Vec<u32> {
+    let mut parts: Vec<u32> = inventory::iter::<Solver>().filter(|solver| solver.day == day)
+                                                         .map(|solver| solver.part)
+                                                         .collect();
+    parts.sort();
+    parts
+}
+
+/// Parse `input` with the parser of `day`.
+pub fn parse(day: u32, input: &str) -> Result<Model> {
+    let parser = parser(day).ok_or(anyhow!("No parser registered for day {}", day))?;
+    (parser.parse)(input)
+}
+
+/// `model` of `day` as JSON.
+pub fn dump(day: u32, model: &Model) -> Result<Value> {
+    let parser = parser(day).ok_or(anyhow!("No parser registered for day {}", day))?;
+    (parser.dump)(model)
+}
+
+/// The model of `day` described by `value`, as printed by `dump`.
+pub fn load(day: u32, value: Value) -> Result<Model> {
+    let parser = parser(day).ok_or(anyhow!("No parser registered for day {}", day))?;
+    (parser.load)(value)
+}
+
+/// Solve `part` of `day` on an already parsed `model`.
+pub fn solve_model(day: u32, part: u32, model: &Model) -> Result<String> {
+    let solver = solver(day, part).ok_or(anyhow!("No solver registered for day {} part {}", day, part))?;
+    (solver.solve)(model)
+}
+
+/// Parse `input` and solve `part` of `day` on it.
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String> {
+    solve_model(day, part, &parse(day, input)?)
+}
@@ -0,0 +1,235 @@
+//! Answer submission, with a local history of every attempt so a known
+//! answer is never sent twice and the website's cooldown is respected.
+//!
+//! The history is a JSON lines file, one [`Attempt`] per line.
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/crisbour/advent-of-code submitter";
+
+/// Outcome of a submission, as reported by the website.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint on the direction
+    Wrong,
+    /// Submitted too soon after the previous answer, nothing was checked
+    Wait,
+    /// The part is already solved, nothing was checked
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Whether the website actually checked the answer.
+    pub fn checked(&self) -> bool {
+        !matches!(self, Verdict::Wait | Verdict::AlreadySolved)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Unix time of the submission
+    pub at: u64,
+    /// Unix time before which no answer may be submitted
+    pub retry_at: u64,
+}
+
+/// Verdict and cooldown in seconds parsed from the page answering a submission.
+pub fn parse_response(page: &str) -> Result<(Verdict, u64)> {
+    let start = page.find("<article").unwrap_or(0);
+    let end = page[start..].find("</article>").map(|end| start + end).unwrap_or(page.len());
+    let text = strip_tags(&page[start..end]);
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::Wait
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        bail!("Unrecognised response: {}", text.trim());
+    };
+    Ok((verdict, cooldown(&text)))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {},
+        }
+    }
+    text.replace("&apos;", "'").replace("&quot;", "\"").replace("&amp;", "&")
+}
+
+fn number(word: &str) -> Option<u64> {
+    let words = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten"];
+    word.parse().ok().or(words.iter().position(|w| *w == word).map(|n| n as u64))
+}
+
+/// Seconds to wait: "please wait one minute" after a wrong answer, or
+/// "You have 1m 20s left to wait" when submitting too soon.
+fn cooldown(text: &str) -> u64 {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if let Some(idx) = words.iter().position(|w| *w == "wait") {
+        if let (Some(cnt), Some(unit)) = (words.get(idx+1).and_then(|w| number(w)), words.get(idx+2)) {
+            if unit.starts_with("minute") {
+                return cnt * 60;
+            }
+        }
+    }
+    if let Some(idx) = words.windows(2).position(|w| w == ["You", "have"]) {
+        return words[idx+2..].iter()
+                             .take_while(|w| **w != "left")
+                             .map(|w| match w.split_at(w.len().saturating_sub(1)) {
+                                 (cnt, "m") => cnt.parse::<u64>().unwrap_or(0) * 60,
+                                 (cnt, "s") => cnt.parse::<u64>().unwrap_or(0),
+                                 _ => 0,
+                             })
+                             .sum();
+    }
+    0
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[derive(Debug, Clone)]
+pub struct Submitter {
+    base_url: String,
+    session: String,
+    year: u32,
+    history: PathBuf,
+}
+
+impl Submitter {
+    pub fn new(session: impl Into<String>, history: impl Into<PathBuf>) -> Self {
+        Self {
+            base_url: BASE_URL.to_string(),
+            session: session.into(),
+            year: crate::input::YEAR,
+            history: history.into(),
+        }
+    }
+
+    /// Server to submit to, `https://adventofcode.com` by default.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_year(mut self, year: u32) -> Self {
+        self.year = year;
+        self
+    }
+
+    /// Every attempt recorded so far, oldest first.
+    pub fn history(&self) -> Result<Vec<Attempt>> {
+        let Ok(content) = fs::read_to_string(&self.history) else { return Ok(Vec::new()) };
+        content.lines()
+               .filter(|line| !line.trim().is_empty())
+               .map(|line| serde_json::from_str(line).with_context(|| format!("Invalid history entry: {}", line)))
+               .collect()
+    }
+
+    fn record(&self, attempt: &Attempt) -> Result<()> {
+        if let Some(dir) = self.history.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(&self.history)?;
+        writeln!(file, "{}", serde_json::to_string(attempt)?)?;
+        Ok(())
+    }
+
+    /// Verdict already known for `answer` from the history: the same answer
+    /// checked before, a solved part, or an answer beyond a too high/too low one.
+    pub fn known_verdict(&self, day: u32, part: u32, answer: &str) -> Result<Option<Verdict>> {
+        let history = self.history()?;
+        let checked = history.iter().filter(|a| a.year == self.year && a.day == day && a.part == part && a.verdict.checked());
+        let value: Option<i128> = answer.parse().ok();
+        for attempt in checked {
+            if attempt.answer == answer {
+                return Ok(Some(attempt.verdict.clone()));
+            }
+            let bound: Option<i128> = attempt.answer.parse().ok();
+            match (&attempt.verdict, value, bound) {
+                (Verdict::Correct, _, _) => return Ok(Some(Verdict::AlreadySolved)),
+                (Verdict::TooHigh, Some(value), Some(bound)) if value >= bound => return Ok(Some(Verdict::TooHigh)),
+                (Verdict::TooLow, Some(value), Some(bound)) if value <= bound => return Ok(Some(Verdict::TooLow)),
+                _ => {},
+            }
+        }
+        Ok(None)
+    }
+
+    /// Whether the history shows `part` of `day` solved.
+    pub fn solved(&self, day: u32, part: u32) -> Result<bool> {
+        Ok(self.history()?.iter().any(|a| a.year == self.year && a.day == day && a.part == part
+                                          && matches!(a.verdict, Verdict::Correct | Verdict::AlreadySolved)))
+    }
+
+    /// Seconds left before the website accepts another answer.
+    pub fn cooldown_left(&self) -> Result<u64> {
+        let retry_at = self.history()?.iter().filter(|a| a.year == self.year).map(|a| a.retry_at).max().unwrap_or(0);
+        Ok(retry_at.saturating_sub(now()))
+    }
+
+    /// Submit `answer` for `part` of `day`, unless its verdict is already
+    /// known. Fails while the cooldown of a previous submission is running.
+    pub fn submit(&self, day: u32, part: u32, answer: &str) -> Result<Verdict> {
+        if let Some(verdict) = self.known_verdict(day, part, answer)? {
+            return Ok(verdict);
+        }
+        let left = self.cooldown_left()?;
+        if left > 0 {
+            bail!("Cooldown running, wait {}s before submitting again", left);
+        }
+        let url = format!("{}/{}/day/{}/answer", self.base_url, self.year, day);
+        let response = ureq::post(&url).set("Cookie", &format!("session={}", self.session))
+                                       .set("User-Agent", USER_AGENT)
+                                       .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        let page = match response {
+            Ok(response) => response.into_string()?,
+            Err(ureq::Error::Status(status, _)) => bail!("Submitting to {} failed with {}", url, status),
+            Err(err) => return Err(anyhow!(err)).with_context(|| format!("Submitting to {} failed", url)),
+        };
+        let (verdict, cooldown) = parse_response(&page)?;
+        let at = now();
+        self.record(&Attempt {
+            year: self.year,
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+            at,
+            retry_at: at + cooldown,
+        })?;
+        Ok(verdict)
+    }
+}
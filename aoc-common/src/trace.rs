@@ -0,0 +1,74 @@
+//! Tracing setup shared by the runner and the day binaries.
+//!
+//! Parsers and solvers run in `parse`, `part1` and `part2` spans added by the
+//! `#[aoc]` attributes, with finer per-item spans and events at the `debug`
+//! and `trace` levels. Without a subscriber, or with their level filtered
+//! out, a disabled callsite costs a cached check and never formats its fields.
+
+use std::env;
+use std::io::{self, IsTerminal};
+#[cfg(feature = "chrome-trace")]
+use std::path::PathBuf;
+use anyhow::{Context, Result};
+use clap::Args;
+#[cfg(feature = "chrome-trace")]
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::EnvFilter;
+
+/// Command line options controlling logs and traces, to be
+/// `#[command(flatten)]`-ed into a `Parser`.
+#[derive(Args, Debug, Clone, Default)]
+pub struct TraceArgs {
+    /// Log filter, e.g. `debug` or `day_5=trace`, default `RUST_LOG` or `warn`
+    #[arg(long, global = true)]
+    pub log: Option<String>,
+    /// Write spans to this file in Chrome trace format, for chrome://tracing,
+    /// Perfetto or speedscope (flamegraphs)
+    #[cfg(feature = "chrome-trace")]
+    #[arg(long, global = true)]
+    pub trace_file: Option<PathBuf>,
+    /// Span filter of the trace file, `trace` for the per-item spans
+    #[cfg(feature = "chrome-trace")]
+    #[arg(long, global = true, default_value = "info")]
+    pub trace_filter: String,
+}
+
+/// Flushes the trace file when dropped, keep it alive until the end of `main`.
+pub struct TraceGuard {
+    #[cfg(feature = "chrome-trace")]
+    _flush: Option<FlushGuard>,
+}
+
+fn filter(directives: &str) -> Result<EnvFilter> {
+    EnvFilter::try_new(directives).with_context(|| format!("Invalid filter: {}", directives))
+}
+
+impl TraceArgs {
+    /// Install the global subscriber: logs to stderr, and spans to the trace
+    /// file if one was asked for, with the `chrome-trace` feature.
+    pub fn init(&self) -> Result<TraceGuard> {
+        let log = self.log.clone().or(env::var("RUST_LOG").ok()).unwrap_or("warn".to_string());
+        let fmt = tracing_subscriber::fmt::layer().without_time()
+                                                  .with_ansi(io::stderr().is_terminal())
+                                                  .with_writer(io::stderr)
+                                                  .with_filter(filter(&log)?);
+        #[cfg(feature = "chrome-trace")]
+        {
+            let (chrome, guard) = match &self.trace_file {
+                Some(path) => {
+                    let (layer, guard) = ChromeLayerBuilder::new().file(path).include_args(true).build();
+                    (Some(layer.with_filter(filter(&self.trace_filter)?)), Some(guard))
+                },
+                None => (None, None),
+            };
+            tracing_subscriber::registry().with(fmt).with(chrome).try_init()?;
+            Ok(TraceGuard { _flush: guard })
+        }
+        #[cfg(not(feature = "chrome-trace"))]
+        {
+            tracing_subscriber::registry().with(fmt).try_init()?;
+            Ok(TraceGuard {})
+        }
+    }
+}
@@ -0,0 +1,344 @@
+//! Frames of a solver at work, exported as an animation to debug grid and
+//! simulation puzzles.
+//!
+//! Solvers hand `push` a closure building a `Frame`: a grid of coloured cells
+//! with markers on some of them. The closure only runs inside `record`, as
+//! `aoc run --visualise` does, so outside of it a frame costs an atomic load.
+//! `export` writes the recorded frames as a GIF, an APNG or a directory of
+//! PNGs.
+//!
+//! Recording and exporting need the `visualise` feature: without it `push`
+//! does nothing, so the day binaries build neither the frames nor the image
+//! encoders.
+
+#[cfg(feature = "visualise")]
+pub use export::*;
+
+/// Most frames kept by `record`, later ones being dropped.
+pub const MAX_FRAMES: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const GREY: Rgb = Rgb(64, 64, 64);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(80, 200, 80);
+    pub const BLUE: Rgb = Rgb(60, 120, 220);
+    pub const YELLOW: Rgb = Rgb(250, 210, 50);
+}
+
+/// Shape drawn over a cell, on top of its colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    /// A square at the centre of the cell
+    Dot,
+    /// Both diagonals of the cell
+    Cross,
+    /// The border of the cell
+    Outline,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Mark {
+    row: usize,
+    column: usize,
+    marker: Marker,
+    colour: Rgb,
+}
+
+/// One picture of the animation: `rows` × `columns` cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    rows: usize,
+    columns: usize,
+    cells: Vec<Rgb>,
+    marks: Vec<Mark>,
+}
+
+impl Frame {
+    /// A frame of `background` cells.
+    pub fn new(rows: usize, columns: usize, background: Rgb) -> Self {
+        Self { rows, columns, cells: vec![background; rows * columns], marks: Vec::new() }
+    }
+
+    /// A frame colouring each cell with `colour(row, column)`.
+    pub fn from_fn(rows: usize, columns: usize, mut colour: impl FnMut(usize, usize) -> Rgb) -> Self {
+        let cells = (0..rows).flat_map(|row| (0..columns).map(move |column| (row, column)))
+                             .map(|(row, column)| colour(row, column))
+                             .collect();
+        Self { rows, columns, cells, marks: Vec::new() }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn cell(&self, row: usize, column: usize) -> Rgb {
+        self.cells[self.index(row, column)]
+    }
+
+    fn index(&self, row: usize, column: usize) -> usize {
+        assert!(row < self.rows && column < self.columns, "cell {},{} outside a {}x{} frame", row, column, self.rows, self.columns);
+        row * self.columns + column
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, colour: Rgb) -> &mut Self {
+        let index = self.index(row, column);
+        self.cells[index] = colour;
+        self
+    }
+
+    pub fn mark(&mut self, row: usize, column: usize, marker: Marker, colour: Rgb) -> &mut Self {
+        self.index(row, column);
+        self.marks.push(Mark { row, column, marker, colour });
+        self
+    }
+
+    /// Outline the cell in yellow.
+    pub fn highlight(&mut self, row: usize, column: usize) -> &mut Self {
+        self.mark(row, column, Marker::Outline, Rgb::YELLOW)
+    }
+
+    /// RGB pixels of the frame, row by row, `cell_size` pixels per cell side.
+    pub fn render(&self, cell_size: u32) -> Vec<u8> {
+        self.render_onto(self.rows, self.columns, cell_size)
+    }
+
+    /// Like `render` on a canvas of `rows` × `columns` cells, black beyond
+    /// the frame.
+    fn render_onto(&self, rows: usize, columns: usize, cell_size: u32) -> Vec<u8> {
+        let size = cell_size.max(1) as usize;
+        let width = columns * size;
+        let mut pixels = vec![0; rows * size * width * 3];
+        let mut paint = |row: usize, column: usize, x: usize, y: usize, Rgb(r, g, b): Rgb| {
+            let at = ((row * size + y) * width + column * size + x) * 3;
+            pixels[at..at + 3].copy_from_slice(&[r, g, b]);
+        };
+        for row in 0..self.rows.min(rows) {
+            for column in 0..self.columns.min(columns) {
+                for (x, y) in (0..size).flat_map(|y| (0..size).map(move |x| (x, y))) {
+                    paint(row, column, x, y, self.cell(row, column));
+                }
+            }
+        }
+        for mark in self.marks.iter().filter(|mark| mark.row < rows && mark.column < columns) {
+            for (x, y) in (0..size).flat_map(|y| (0..size).map(move |x| (x, y))) {
+                let inside = match mark.marker {
+                    Marker::Dot => (size / 4..size - size / 4).contains(&x) && (size / 4..size - size / 4).contains(&y),
+                    Marker::Cross => x == y || x + y == size - 1,
+                    Marker::Outline => x == 0 || y == 0 || x == size - 1 || y == size - 1,
+                };
+                if inside {
+                    paint(mark.row, mark.column, x, y, mark.colour);
+                }
+            }
+        }
+        pixels
+    }
+}
+
+/// Whether frames pushed now are recorded, never without the `visualise`
+/// feature.
+#[cfg(not(feature = "visualise"))]
+pub fn enabled() -> bool {
+    false
+}
+
+/// Record the frame built by `frame`, a no-op without the `visualise`
+/// feature.
+#[cfg(not(feature = "visualise"))]
+pub fn push(_frame: impl FnOnce() -> Frame) {}
+
+#[cfg(feature = "visualise")]
+mod export {
+    use std::collections::BTreeSet;
+    use std::fs::{self, File};
+    use std::io::BufWriter;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use anyhow::{bail, Context, Result};
+    use clap::Args;
+    use tracing::warn;
+    use super::{Frame, Rgb, MAX_FRAMES};
+
+    static RECORDING: AtomicBool = AtomicBool::new(false);
+    static FRAMES: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+    /// Frames pushed past `MAX_FRAMES` during the current recording
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    /// Whether frames pushed now are recorded.
+    pub fn enabled() -> bool {
+        RECORDING.load(Ordering::Relaxed)
+    }
+
+    /// Record the frame built by `frame`, only called while recording.
+    pub fn push(frame: impl FnOnce() -> Frame) {
+        if !enabled() {
+            return;
+        }
+        let frame = frame();
+        let mut frames = FRAMES.lock().unwrap();
+        match frames.len() < MAX_FRAMES {
+            true => frames.push(frame),
+            false => { DROPPED.fetch_add(1, Ordering::Relaxed); },
+        }
+    }
+
+    /// Stops the recording when dropped, even by a panic.
+    struct Recording;
+
+    impl Drop for Recording {
+        fn drop(&mut self) {
+            RECORDING.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Run `f` and collect the first `MAX_FRAMES` frames it pushes. Recording
+    /// is process wide: frames pushed by other threads meanwhile are collected
+    /// too.
+    pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Frame>) {
+        FRAMES.lock().unwrap().clear();
+        DROPPED.store(0, Ordering::Relaxed);
+        RECORDING.store(true, Ordering::Relaxed);
+        let recording = Recording;
+        let result = f();
+        drop(recording);
+        let dropped = DROPPED.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            warn!("Dropped {} frames past the first {}", dropped, MAX_FRAMES);
+        }
+        (result, std::mem::take(&mut *FRAMES.lock().unwrap()))
+    }
+
+    /// How `export` writes the frames, from the extension of its path.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Animation {
+        /// `.gif`
+        Gif,
+        /// `.png` or `.apng`, a plain PNG for a single frame
+        Apng,
+        /// Any other path: a directory of `frame-00000.png`, `frame-00001.png`, ...
+        PngSequence,
+    }
+
+    impl Animation {
+        pub fn from_path(path: &Path) -> Self {
+            match path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref() {
+                Some("gif") => Animation::Gif,
+                Some("png" | "apng") => Animation::Apng,
+                _ => Animation::PngSequence,
+            }
+        }
+    }
+
+    /// Write `frames` to `path`, each shown for `delay`, as the `Animation` its
+    /// extension asks for. Frames of different sizes are drawn from the top left
+    /// corner of a canvas fitting them all.
+    pub fn export(frames: &[Frame], path: &Path, cell_size: u32, delay: Duration) -> Result<()> {
+        if frames.is_empty() {
+            bail!("No frames to export");
+        }
+        let rows = frames.iter().map(Frame::rows).max().unwrap();
+        let columns = frames.iter().map(Frame::columns).max().unwrap();
+        let size = cell_size.max(1);
+        let (width, height) = (columns as u32 * size, rows as u32 * size);
+        if width == 0 || height == 0 || width > u16::MAX as u32 || height > u16::MAX as u32 {
+            bail!("Can't export {}x{} pixel frames", width, height);
+        }
+        let pixels = frames.iter().map(|frame| frame.render_onto(rows, columns, size));
+        let written = match Animation::from_path(path) {
+            Animation::Gif => {
+                let colours: BTreeSet<Rgb> = frames.iter().flat_map(|frame| frame.cells.iter().chain(frame.marks.iter().map(|mark| &mark.colour)))
+                                                   .copied()
+                                                   .chain([Rgb::BLACK])
+                                                   .collect();
+                let palette = (colours.len() <= 256).then(|| colours.into_iter().collect());
+                write_gif(path, width as u16, height as u16, palette, pixels, delay)
+            },
+            Animation::Apng => write_apng(path, width, height, pixels, frames.len() as u32, delay),
+            Animation::PngSequence => {
+                fs::create_dir_all(path).map_err(anyhow::Error::from).and_then(|_| {
+                    pixels.enumerate().try_for_each(|(i, pixels)| write_apng(&path.join(format!("frame-{:05}.png", i)), width, height, [pixels].into_iter(), 1, delay))
+                })
+            },
+        };
+        written.with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// A GIF indexing `palette` when all the colours fit in one, quantizing
+    /// each frame otherwise.
+    fn write_gif(path: &Path, width: u16, height: u16, palette: Option<Vec<Rgb>>, pixels: impl Iterator<Item = Vec<u8>>, delay: Duration) -> Result<()> {
+        let flat: Vec<u8> = palette.iter().flatten().flat_map(|&Rgb(r, g, b)| [r, g, b]).collect();
+        let mut encoder = gif::Encoder::new(BufWriter::new(File::create(path)?), width, height, &flat)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for frame in pixels {
+            let mut gif_frame = match &palette {
+                Some(palette) => {
+                    let indices = frame.chunks_exact(3).map(|rgb| palette.binary_search(&Rgb(rgb[0], rgb[1], rgb[2])).unwrap() as u8).collect();
+                    gif::Frame { width, height, buffer: indices, ..gif::Frame::default() }
+                },
+                None => gif::Frame::from_rgb_speed(width, height, &frame, 10),
+            };
+            gif_frame.delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+            encoder.write_frame(&gif_frame)?;
+        }
+        Ok(())
+    }
+
+    fn write_apng(path: &Path, width: u32, height: u32, pixels: impl Iterator<Item = Vec<u8>>, count: u32, delay: Duration) -> Result<()> {
+        let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        if count > 1 {
+            encoder.set_animated(count, 0)?;
+            encoder.set_frame_delay(delay.as_millis().min(u16::MAX as u128) as u16, 1000)?;
+        }
+        let mut writer = encoder.write_header()?;
+        for frame in pixels {
+            writer.write_image_data(&frame)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Command line options recording the frames of a run, to be
+    /// `#[command(flatten)]`-ed into a `Parser`.
+    #[derive(Args, Debug, Clone, Default)]
+    pub struct VisualiseArgs {
+        /// Write the frames pushed by the solvers to this `.gif`, `.png` (APNG)
+        /// or directory of PNGs
+        #[arg(long, value_name = "PATH")]
+        pub visualise: Option<PathBuf>,
+        /// Side of a cell in the exported frames, in pixels
+        #[arg(long, value_name = "PX", default_value_t = 4)]
+        pub cell_size: u32,
+        /// Time each frame is shown, in milliseconds
+        #[arg(long, value_name = "MS", default_value_t = 100)]
+        pub frame_delay: u64,
+    }
+
+    impl VisualiseArgs {
+        /// Run `f`, then export the frames it pushed when asked to.
+        pub fn record<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+            let Some(path) = &self.visualise else {
+                return f();
+            };
+            let (result, frames) = record(f);
+            let result = result?;
+            if frames.is_empty() {
+                bail!("The solvers pushed no frames to visualise");
+            }
+            export(&frames, path, self.cell_size, Duration::from_millis(self.frame_delay))?;
+            Ok(result)
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Allocation counting, with `Counting` as the allocator of this test binary.
+
+use std::hint::black_box;
+use aoc_common::alloc::{self, Counting};
+
+#[global_allocator]
+static ALLOC: Counting = Counting;
+
+// A single test: counters are process wide and tests run on parallel threads.
+#[test]
+fn counts_allocations_and_peak() {
+    let (len, stats) = alloc::measure(|| black_box(vec![0u8; 4096]).len());
+    let stats = stats.expect("Counting is installed");
+    assert_eq!(len, 4096);
+    assert_eq!(stats.allocs, 1);
+    assert_eq!(stats.bytes, 4096);
+    assert_eq!(stats.peak_bytes, 4096);
+
+    let (_, stats) = alloc::measure(|| {
+        for _ in 0..3 {
+            black_box(vec![0u8; 1000]);
+        }
+    });
+    let stats = stats.unwrap();
+    assert_eq!(stats.allocs, 3);
+    assert_eq!(stats.bytes, 3000);
+    assert_eq!(stats.peak_bytes, 1000);
+}
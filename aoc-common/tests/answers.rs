@@ -0,0 +1,28 @@
+//! Checks against the locked answers of `answers.toml`.
+
+use std::fs;
+use aoc_common::answers;
+
+#[test]
+fn loads_locked_answers() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("answers.toml"), "part1 = \"42\"\n").unwrap();
+    assert_eq!(answers::load(dir.path()).unwrap().get("part1").map(String::as_str), Some("42"));
+    assert!(answers::load(dir.path().join("missing")).unwrap().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "No input in")]
+fn fails_days_without_input() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("input"), "").unwrap();
+    answers::check(dir.path(), 1, 1);
+}
+
+#[test]
+#[should_panic(expected = "No locked answer for part 1")]
+fn fails_parts_without_a_locked_answer() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("input"), "1\n").unwrap();
+    answers::check(dir.path(), 1, 1);
+}
@@ -0,0 +1,44 @@
+//! Local stand-in for the puzzle website.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_http::{Header, Response, Server};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+pub type Log = Arc<Mutex<Vec<Request>>>;
+
+/// Serve `body` with `status` to every request until the test ends,
+/// returning the server's base URL and the log of requests.
+pub fn serve(status: u16, body: &'static str) -> (String, Log) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let log: Log = Arc::default();
+    let requests = Arc::clone(&log);
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let cookie = request.headers()
+                                .iter()
+                                .find(|header| header.field.equiv("Cookie"))
+                                .map(|header| header.value.to_string());
+            let mut content = String::new();
+            request.as_reader().read_to_string(&mut content).unwrap();
+            requests.lock().unwrap().push(Request {
+                method: request.method().to_string(),
+                path: request.url().to_string(),
+                cookie,
+                body: content,
+            });
+            let response = Response::from_string(body).with_status_code(status)
+                                                      .with_header(Header::from_bytes("Content-Type", "text/html").unwrap());
+            request.respond(response).unwrap();
+        }
+    });
+    (url, log)
+}
@@ -0,0 +1,33 @@
+use anyhow::anyhow;
+use aoc_common::diagnostic::{in_file, Diagnostic};
+
+#[test]
+fn renders_excerpt_caret_and_hint() {
+    let input = "Card 1: 41 48 | 83 86\nCard 2: 1 xyz | 3\n";
+    let diagnostic = Diagnostic::new(input, 32..35, "invalid number").with_hint("expected a u32")
+                                                                       .with_file("day-4/input");
+    assert_eq!((diagnostic.line, diagnostic.column), (2, 11));
+    assert_eq!(diagnostic.to_string(), "invalid number\n\
+                                        \x20--> day-4/input:2:11\n\
+                                        \x20 |\n\
+                                        2 | Card 2: 1 xyz | 3\n\
+                                        \x20 |           ^^^\n\
+                                        \x20 = hint: expected a u32");
+}
+
+#[test]
+fn underlines_within_the_line() {
+    let input = "é\tab\ncd\n";
+    let diagnostic = Diagnostic::new(input, 3..9, "bad");
+    assert_eq!(diagnostic.column, 3);
+    assert_eq!(diagnostic.to_string(), "bad\n --> <input>:1:3\n  |\n1 | é\tab\n  |  \t^^");
+    let empty = Diagnostic::new(input, input.len()..input.len(), "missing");
+    assert!(empty.to_string().ends_with("3 | \n  | ^"), "{}", empty);
+}
+
+#[test]
+fn names_the_file_of_diagnostics_only() {
+    let err = in_file(Diagnostic::new("x\n", 0..1, "bad").into(), "input");
+    assert_eq!(err.downcast_ref::<Diagnostic>().unwrap().file.as_deref(), Some("input"));
+    assert_eq!(in_file(anyhow!("other"), "input").to_string(), "other");
+}
@@ -0,0 +1,23 @@
+//! Example extraction and checks.
+
+use std::fs;
+use aoc_common::examples::{self, Example};
+
+#[test]
+fn overrides_extraction_with_examples_toml() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("puzzle.md"), "```\n1 2\n\n```\ngives `*3*`.\n--- Part Two ---\nnow `*2*`.\n").unwrap();
+    fs::write(dir.path().join("examples.toml"), "[part2]\ninput = \"4 5\\n\"\n").unwrap();
+    assert_eq!(examples::load(dir.path()).unwrap(), [
+        Example { part: 1, input: "1 2\n".to_string(), answer: "3".to_string() },
+        Example { part: 2, input: "4 5\n".to_string(), answer: "2".to_string() },
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "No example for part 2")]
+fn fails_parts_without_an_example() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("examples.toml"), "[part1]\ninput = \"1\\n\"\nanswer = \"1\"\n").unwrap();
+    examples::check(dir.path(), 1, 2);
+}
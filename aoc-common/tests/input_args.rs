@@ -0,0 +1,41 @@
+//! Inputs chosen on the command line.
+
+use std::fs;
+use aoc_common::input::{Input, InputArgs};
+use anyhow::anyhow;
+
+fn args(inputs: &[&str]) -> InputArgs {
+    InputArgs { inputs: inputs.iter().map(|input| input.to_string()).collect() }
+}
+
+#[test]
+fn defaults_to_the_day_input() {
+    let inputs = args(&[]).load(|| Ok("day input\n".to_string())).unwrap();
+    assert_eq!(inputs, [Input { name: "input".to_string(), text: "day input\n".to_string() }]);
+}
+
+#[test]
+fn reads_paths_and_globs_in_order() {
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["b.txt", "a.txt", "example"] {
+        fs::write(dir.path().join(name), name).unwrap();
+    }
+    let example = dir.path().join("example").display().to_string();
+    let glob = dir.path().join("*.txt").display().to_string();
+
+    let inputs = args(&[&example, &glob]).load(|| Err(anyhow!("not used"))).unwrap();
+    let texts: Vec<&str> = inputs.iter().map(|input| input.text.as_str()).collect();
+    assert_eq!(texts, ["example", "a.txt", "b.txt"]);
+    assert_eq!(inputs[0].name, example);
+}
+
+#[test]
+fn missing_files_are_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing").display().to_string();
+    let glob = dir.path().join("*.txt").display().to_string();
+
+    assert!(args(&[&missing]).load(|| Ok(String::new())).is_err());
+    let err = args(&[&glob]).load(|| Ok(String::new())).unwrap_err();
+    assert!(err.to_string().contains("No file matches"), "{}", err);
+}
@@ -0,0 +1,72 @@
+//! Input provider against a local stand-in for the puzzle website.
+
+mod common;
+
+use std::fs;
+use aoc_common::input::Provider;
+use common::serve;
+
+#[test]
+fn fetches_once_then_reads_cache() {
+    let (url, log) = serve(200, "1abc2\n");
+    let cache = tempfile::tempdir().unwrap();
+    let provider = Provider::new(cache.path()).with_base_url(url).with_session("secret");
+
+    assert_eq!(provider.input(1).unwrap(), "1abc2\n");
+    assert_eq!(fs::read_to_string(provider.cache_path(1)).unwrap(), "1abc2\n");
+    assert_eq!(provider.input(1).unwrap(), "1abc2\n");
+
+    let log = log.lock().unwrap();
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].path, "/2023/day/1/input");
+    assert_eq!(log[0].cookie.as_deref(), Some("session=secret"));
+}
+
+#[test]
+fn prefers_local_input() {
+    let (url, log) = serve(200, "remote\n");
+    let cache = tempfile::tempdir().unwrap();
+    let day_dir = tempfile::tempdir().unwrap();
+    fs::write(day_dir.path().join("input"), "local\n").unwrap();
+    let provider = Provider::new(cache.path()).with_base_url(url)
+                                              .with_session("secret")
+                                              .with_local_dir(day_dir.path());
+
+    assert_eq!(provider.input(4).unwrap(), "local\n");
+    assert!(log.lock().unwrap().is_empty());
+}
+
+#[test]
+fn empty_local_input_is_a_placeholder() {
+    let (url, _log) = serve(200, "remote\n");
+    let cache = tempfile::tempdir().unwrap();
+    let day_dir = tempfile::tempdir().unwrap();
+    fs::write(day_dir.path().join("input"), "").unwrap();
+    let provider = Provider::new(cache.path()).with_base_url(url)
+                                              .with_session("secret")
+                                              .with_local_dir(day_dir.path());
+
+    assert_eq!(provider.input(8).unwrap(), "remote\n");
+}
+
+#[test]
+fn missing_session_is_an_error() {
+    let (url, log) = serve(200, "remote\n");
+    let cache = tempfile::tempdir().unwrap();
+    let provider = Provider::new(cache.path()).with_base_url(url);
+
+    let err = provider.input(2).unwrap_err();
+    assert!(err.to_string().contains("no session"), "{}", err);
+    assert!(log.lock().unwrap().is_empty());
+}
+
+#[test]
+fn server_error_is_not_cached() {
+    let (url, _log) = serve(400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.");
+    let cache = tempfile::tempdir().unwrap();
+    let provider = Provider::new(cache.path()).with_base_url(url).with_session("expired");
+
+    let err = provider.input(3).unwrap_err();
+    assert!(err.to_string().contains("400"), "{}", err);
+    assert!(!provider.cache_path(3).exists());
+}
@@ -0,0 +1,63 @@
+use std::borrow::Cow;
+use aoc_common::diagnostic::Diagnostic;
+use aoc_common::normalise::{normalise, Fix, Normalised};
+use aoc_common::parse::{lines, Span};
+
+#[test]
+fn keeps_clean_inputs_borrowed() {
+    for input in ["", "a\n", "seeds: 1 2\n\nmap:\n3 4\n"] {
+        let Normalised { text, fixes, .. } = normalise(input);
+        assert!(matches!(text, Cow::Borrowed(_)), "{:?}", input);
+        assert_eq!(text, input);
+        assert!(fixes.is_empty());
+    }
+}
+
+#[test]
+fn fixes_windows_inputs() {
+    let Normalised { text, fixes, .. } = normalise("\u{feff}seeds: 1 2\r\n\r\nmap:\r\n3 4");
+    assert_eq!(text, "seeds: 1 2\n\nmap:\n3 4\n");
+    assert_eq!(fixes, [Fix::ByteOrderMark, Fix::MissingFinalNewline, Fix::CrlfLineEndings]);
+}
+
+#[test]
+fn fixes_stray_blank_lines_and_whitespace() {
+    let Normalised { text, fixes, .. } = normalise("\n \na  \n\n\t\nb\n\n\n");
+    assert_eq!(text, "a\n\nb\n");
+    assert_eq!(fixes, [Fix::TrailingWhitespace, Fix::LeadingBlankLines, Fix::RepeatedBlankLines, Fix::TrailingBlankLines]);
+}
+
+#[test]
+fn blank_inputs_become_empty() {
+    let Normalised { text, fixes, .. } = normalise("\r\n  \n");
+    assert_eq!(text, "");
+    assert_eq!(fixes, [Fix::CrlfLineEndings, Fix::TrailingWhitespace, Fix::LeadingBlankLines]);
+}
+
+#[test]
+fn locates_diagnostics_in_the_original_input() {
+    let input = "\u{feff}\r\n\r\n\r\nCard 1: 1 2 | 3\r\n\r\n\r\nCard 2: 1 x | 3  \r\n";
+    let normalised = normalise(input);
+    assert_eq!(normalised.text, "Card 1: 1 2 | 3\n\nCard 2: 1 x | 3\n");
+    let line = lines(Span::new(&normalised.text)).nth(2).unwrap();
+    let err = normalised.locate(line.slice(10..11).error("invalid number").with_hint("expected a u32").into());
+    let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+    assert_eq!((diagnostic.line, diagnostic.column), (7, 11));
+    assert_eq!(&input[diagnostic.span.clone()], "x");
+    assert_eq!((diagnostic.excerpt.as_str(), diagnostic.hint.as_deref()), ("Card 2: 1 x | 3  ", Some("expected a u32")));
+
+    let err = normalised.locate(Span::new(&normalised.text).slice(0..4).error("bad").into());
+    let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+    assert_eq!((diagnostic.line, diagnostic.column, diagnostic.excerpt.as_str()), (4, 1, "Card 1: 1 2 | 3"));
+}
+
+#[test]
+fn locates_diagnostics_on_a_first_line_after_a_byte_order_mark() {
+    let input = "\u{feff}Time: x\r\n";
+    let normalised = normalise(input);
+    let end = normalised.text.len();
+    let err = normalised.locate(Span::new(&normalised.text).slice(6..7).error("bad").into());
+    let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+    assert_eq!((diagnostic.line, diagnostic.column, diagnostic.excerpt.as_str()), (1, 7, "Time: x"));
+    assert_eq!(normalised.original_offset(end), input.find('\r').unwrap());
+}
@@ -0,0 +1,69 @@
+//! Rendering of result records.
+
+use aoc_common::alloc::AllocStats;
+use aoc_common::output::{input_hash, Format, Record};
+
+fn record(answer: &str, expected: Option<&str>) -> Record {
+    Record {
+        year: 2023,
+        day: 7,
+        part: 2,
+        answer: answer.to_string(),
+        expected: expected.map(str::to_string),
+        parse_ns: 1500,
+        solve_ns: 42,
+        input_hash: input_hash("abc"),
+        parse_alloc: None,
+        solve_alloc: None,
+        input: None,
+    }
+}
+
+#[test]
+fn hashes_input_with_sha256() {
+    assert_eq!(input_hash("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+}
+
+#[test]
+fn renders_text() {
+    assert_eq!(record("5905", None).render(Format::Text), "Day 7 part 2: 5905");
+    assert_eq!(record("5905", Some("5905")).render(Format::Text), "Day 7 part 2 example: 5905");
+    assert_eq!(record("5904", Some("5905")).render(Format::Text), "Day 7 part 2 example: 5904 (expected 5905)");
+}
+
+#[test]
+fn renders_json_lines() {
+    let line = record("5905", None).render(Format::Json);
+    assert!(!line.contains('\n'));
+    assert_eq!(serde_json::from_str::<Record>(&line).unwrap(), record("5905", None));
+}
+
+#[test]
+fn renders_csv() {
+    assert_eq!(Format::Csv.header(), Some("year,day,part,answer,expected,parse_ns,solve_ns,input_hash,\
+                                           parse_allocs,parse_bytes,parse_peak_bytes,solve_allocs,solve_bytes,solve_peak_bytes,input"));
+    assert_eq!(record("5905", Some("5905")).render(Format::Csv),
+               format!("2023,7,2,5905,5905,1500,42,{},,,,,,,", input_hash("abc")));
+    assert!(record("a,\"b\"", None).render(Format::Csv).starts_with("2023,7,2,\"a,\"\"b\"\"\",,1500,"));
+}
+
+#[test]
+fn renders_allocations_next_to_timings() {
+    let mut record = record("5905", None);
+    record.parse_alloc = Some(AllocStats { allocs: 3, bytes: 2048, peak_bytes: 1024 });
+    record.solve_alloc = Some(AllocStats { allocs: 1, bytes: 16, peak_bytes: 16 });
+
+    assert_eq!(record.render(Format::Text),
+               "Day 7 part 2: 5905  [parse 0.002 ms, 3 allocs, 2.0 KiB, peak 1.0 KiB; solve 0.000 ms, 1 allocs, 16 B, peak 16 B]");
+    assert!(record.render(Format::Csv).ends_with(",3,2048,1024,1,16,16,"));
+    assert_eq!(serde_json::from_str::<Record>(&record.render(Format::Json)).unwrap(), record);
+}
+
+#[test]
+fn names_inputs_given_on_the_command_line() {
+    let mut record = record("5905", None);
+    record.input = Some("teammate.txt".to_string());
+
+    assert_eq!(record.render(Format::Text), "Day 7 part 2 (teammate.txt): 5905");
+    assert!(record.render(Format::Csv).ends_with(",teammate.txt"));
+}
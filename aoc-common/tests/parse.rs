@@ -0,0 +1,72 @@
+use aoc_common::parse::{blocks, field, grid, int, ints, label, labelled, lines, pair, separated, single_block, tokens, Span};
+
+fn texts<'a>(spans: impl Iterator<Item = Span<'a>>) -> Vec<&'a str> {
+    spans.map(|span| span.as_str()).collect()
+}
+
+#[test]
+fn splits_lines_and_blocks() {
+    let input = Span::new("seeds: 1 2\n\na:\n1 2 3\n4 5 6\n");
+    assert_eq!(texts(lines(input)), ["seeds: 1 2", "", "a:", "1 2 3", "4 5 6"]);
+    assert_eq!(texts(blocks(input)), ["seeds: 1 2", "a:\n1 2 3\n4 5 6"]);
+    assert_eq!(texts(lines(Span::new(""))), Vec::<&str>::new());
+    assert_eq!(texts(blocks(Span::new(""))), Vec::<&str>::new());
+}
+
+#[test]
+fn rejects_content_after_a_single_block() {
+    assert_eq!(single_block(Span::new("a\nb\n")).unwrap().as_str(), "a\nb");
+    assert_eq!(single_block(Span::new("")).unwrap().as_str(), "");
+    let err = single_block(Span::new("a\n\nb\nc\n")).unwrap_err();
+    assert_eq!((err.line, err.column, err.excerpt.as_str()), (3, 1, "b"));
+    assert_eq!(err.message, "unexpected content after a blank line");
+}
+
+#[test]
+fn parses_labelled_sections() {
+    let input = Span::new("Card  12: 41 48 | 83  86 6\n");
+    let line = lines(input).next().unwrap();
+    let (id, numbers) = labelled::<u32>(line, "Card").unwrap();
+    let (winning, own) = pair(numbers, '|').unwrap();
+    assert_eq!(id, 12);
+    assert_eq!(ints::<u32>(winning).unwrap(), [41, 48]);
+    assert_eq!(ints::<u32>(own).unwrap(), [83, 86, 6]);
+
+    let (name, content) = label(Span::new("seed-to-soil map:\n50 98 2")).unwrap();
+    assert_eq!((name.as_str(), content.as_str()), ("seed-to-soil map", "50 98 2"));
+    assert_eq!(field(Span::new("Time:  7 15"), "Time").unwrap().as_str(), "7 15");
+    assert_eq!(texts(separated(Span::new("3 blue, 4 red; 1 red"), ";")), ["3 blue, 4 red", "1 red"]);
+    let [hand, bid] = tokens(Span::new("32T3K 765")).unwrap();
+    assert_eq!((hand.as_str(), int::<u32>(bid).unwrap()), ("32T3K", 765));
+}
+
+#[test]
+fn locates_errors() {
+    let input = Span::new("Card 1: 1 2 | 3\nCard 2: 1 x | 3\n");
+    let line = lines(input).nth(1).unwrap();
+    let (_, numbers) = labelled::<u32>(line, "Card").unwrap();
+    let (winning, _) = pair(numbers, '|').unwrap();
+    let err = ints::<u32>(winning).unwrap_err();
+    assert_eq!((err.line, err.column, err.span), (2, 11, 26..27));
+    assert_eq!(err.message, "invalid number \"x\": invalid digit found in string");
+    assert_eq!(err.hint.as_deref(), Some("expected a u32"));
+
+    let err = pair(Span::new("1 | 2 | 3"), '|').unwrap_err();
+    assert_eq!((err.column, err.message.as_str()), (7, "unexpected second `|`"));
+    let err = labelled::<u32>(Span::new("Game 1: x"), "Card").unwrap_err();
+    assert_eq!(err.message, "expected `Card <id>:`");
+    let err = field(Span::new("Distance: 9"), "Time").unwrap_err();
+    assert_eq!(err.message, "expected `Time:`");
+    let err = tokens::<2>(Span::new("a b c")).unwrap_err();
+    assert_eq!(err.message, "expected 2 words, found 3");
+}
+
+#[test]
+fn checks_grids_are_rectangular() {
+    let rows = grid(Span::new(".*.\n1.2\n")).unwrap();
+    assert_eq!(texts(rows.into_iter()), [".*.", "1.2"]);
+    let err = grid(Span::new("...\n..\n")).unwrap_err();
+    assert_eq!((err.line, err.excerpt.as_str()), (2, ".."));
+    assert_eq!(err.message, "row is 2 cells wide, expected 3");
+    assert!(grid(Span::new("")).is_err());
+}
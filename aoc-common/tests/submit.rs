@@ -0,0 +1,90 @@
+//! Submission client against a local stand-in replaying recorded pages.
+
+mod common;
+
+use std::fs;
+use aoc_common::submit::{parse_response, Attempt, Submitter, Verdict};
+use common::serve;
+
+const CORRECT: &str = include_str!("pages/correct.html");
+const TOO_HIGH: &str = include_str!("pages/too_high.html");
+const TOO_LOW: &str = include_str!("pages/too_low.html");
+const TOO_RECENTLY: &str = include_str!("pages/too_recently.html");
+const WRONG_LEVEL: &str = include_str!("pages/wrong_level.html");
+
+#[test]
+fn parses_recorded_pages() {
+    assert_eq!(parse_response(CORRECT).unwrap(), (Verdict::Correct, 0));
+    assert_eq!(parse_response(TOO_HIGH).unwrap(), (Verdict::TooHigh, 60));
+    assert_eq!(parse_response(TOO_LOW).unwrap(), (Verdict::TooLow, 300));
+    assert_eq!(parse_response(TOO_RECENTLY).unwrap(), (Verdict::Wait, 80));
+    assert_eq!(parse_response(WRONG_LEVEL).unwrap(), (Verdict::AlreadySolved, 0));
+}
+
+#[test]
+fn posts_answer_and_records_it() {
+    let (url, log) = serve(200, CORRECT);
+    let dir = tempfile::tempdir().unwrap();
+    let submitter = Submitter::new("secret", dir.path().join("history.jsonl")).with_base_url(url);
+
+    assert_eq!(submitter.submit(7, 2, "5905").unwrap(), Verdict::Correct);
+    {
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].method, "POST");
+        assert_eq!(log[0].path, "/2023/day/7/answer");
+        assert_eq!(log[0].cookie.as_deref(), Some("session=secret"));
+        assert_eq!(log[0].body, "level=2&answer=5905");
+    }
+    let history = submitter.history().unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!((history[0].day, history[0].part, history[0].answer.as_str()), (7, 2, "5905"));
+
+    // Solved parts are never submitted again
+    assert_eq!(submitter.submit(7, 2, "1234").unwrap(), Verdict::AlreadySolved);
+    assert_eq!(log.lock().unwrap().len(), 1);
+    assert!(submitter.solved(7, 2).unwrap());
+    assert!(!submitter.solved(7, 1).unwrap());
+}
+
+#[test]
+fn honours_cooldown_and_known_answers() {
+    let (url, log) = serve(200, TOO_HIGH);
+    let dir = tempfile::tempdir().unwrap();
+    let submitter = Submitter::new("secret", dir.path().join("history.jsonl")).with_base_url(url);
+
+    assert_eq!(submitter.submit(7, 1, "7000").unwrap(), Verdict::TooHigh);
+    assert!(submitter.cooldown_left().unwrap() > 55);
+
+    // Known verdicts are answered from the history, even during the cooldown
+    assert_eq!(submitter.submit(7, 1, "7000").unwrap(), Verdict::TooHigh);
+    assert_eq!(submitter.submit(7, 1, "8000").unwrap(), Verdict::TooHigh);
+    let err = submitter.submit(7, 1, "6000").unwrap_err();
+    assert!(err.to_string().contains("Cooldown"), "{}", err);
+    assert_eq!(log.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn wait_page_is_not_a_verdict() {
+    let (url, log) = serve(200, TOO_RECENTLY);
+    let dir = tempfile::tempdir().unwrap();
+    let history = dir.path().join("history.jsonl");
+    let old = Attempt {
+        year: 2023,
+        day: 6,
+        part: 1,
+        answer: "100".to_string(),
+        verdict: Verdict::TooLow,
+        at: 0,
+        retry_at: 0,
+    };
+    fs::write(&history, serde_json::to_string(&old).unwrap() + "\n").unwrap();
+    let submitter = Submitter::new("secret", &history).with_base_url(url);
+
+    assert_eq!(submitter.submit(6, 1, "50").unwrap(), Verdict::TooLow);
+    assert_eq!(submitter.submit(6, 1, "200").unwrap(), Verdict::Wait);
+    assert_eq!(log.lock().unwrap().len(), 1);
+    // The wait page only sets the cooldown, the answer stays unknown
+    assert_eq!(submitter.known_verdict(6, 1, "200").unwrap(), None);
+    assert!(submitter.cooldown_left().unwrap() > 75);
+}
@@ -0,0 +1,108 @@
+//! Frames recorded from `push`, rendered and exported.
+
+use std::fs::File;
+use std::time::Duration;
+use aoc_common::visualise::{self, Animation, Frame, Marker, Rgb};
+
+fn checkerboard() -> Frame {
+    Frame::from_fn(2, 3, |row, column| match (row + column) % 2 {
+        0 => Rgb::WHITE,
+        _ => Rgb::BLUE,
+    })
+}
+
+// The only test recording: recording is process wide and tests run on
+// parallel threads.
+#[test]
+fn records_frames_only_while_recording() {
+    visualise::push(|| panic!("built a frame while not recording"));
+    let (answer, frames) = visualise::record(|| {
+        assert!(visualise::enabled());
+        for i in 0..3 {
+            visualise::push(|| Frame::new(1, i + 1, Rgb::GREY));
+        }
+        42
+    });
+    assert_eq!(answer, 42);
+    assert_eq!(frames.iter().map(Frame::columns).collect::<Vec<_>>(), [1, 2, 3]);
+    assert!(!visualise::enabled());
+    assert_eq!(visualise::record(|| ()).1, []);
+
+    let panicked = std::panic::catch_unwind(|| visualise::record(|| panic!("solver failed")));
+    assert!(panicked.is_err());
+    assert!(!visualise::enabled());
+
+    let (_, frames) = visualise::record(|| (0..visualise::MAX_FRAMES + 5).for_each(|_| visualise::push(|| Frame::new(1, 1, Rgb::GREY))));
+    assert_eq!(frames.len(), visualise::MAX_FRAMES);
+}
+
+#[test]
+fn renders_cells_and_markers() {
+    let mut frame = checkerboard();
+    frame.set(1, 2, Rgb::RED).mark(0, 1, Marker::Dot, Rgb::GREEN).highlight(1, 0);
+    assert_eq!(frame.cell(1, 2), Rgb::RED);
+
+    let pixels = frame.render(4);
+    assert_eq!(pixels.len(), 2 * 4 * 3 * 4 * 3);
+    let pixel = |x: usize, y: usize| {
+        let at = (y * 12 + x) * 3;
+        Rgb(pixels[at], pixels[at + 1], pixels[at + 2])
+    };
+    assert_eq!(pixel(0, 0), Rgb::WHITE);
+    assert_eq!(pixel(4, 0), Rgb::BLUE);
+    assert_eq!(pixel(5, 1), Rgb::GREEN);
+    assert_eq!(pixel(4, 3), Rgb::BLUE);
+    assert_eq!(pixel(0, 4), Rgb::YELLOW);
+    assert_eq!(pixel(1, 5), Rgb::BLUE);
+    assert_eq!(pixel(11, 7), Rgb::RED);
+}
+
+#[test]
+#[should_panic(expected = "outside a 2x3 frame")]
+fn rejects_cells_outside_the_frame() {
+    checkerboard().set(2, 0, Rgb::RED);
+}
+
+#[test]
+fn picks_the_animation_from_the_extension() {
+    assert_eq!(Animation::from_path("out.gif".as_ref()), Animation::Gif);
+    assert_eq!(Animation::from_path("out.PNG".as_ref()), Animation::Apng);
+    assert_eq!(Animation::from_path("out.apng".as_ref()), Animation::Apng);
+    assert_eq!(Animation::from_path("frames".as_ref()), Animation::PngSequence);
+}
+
+#[test]
+fn exports_gif_apng_and_png_sequence() {
+    let dir = tempfile::tempdir().unwrap();
+    let frames = [checkerboard(), Frame::new(3, 1, Rgb::GREEN)];
+
+    let path = dir.path().join("out.gif");
+    visualise::export(&frames, &path, 2, Duration::from_millis(200)).unwrap();
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (6, 6));
+    let first = decoder.read_next_frame().unwrap().unwrap();
+    assert_eq!(first.delay, 20);
+    assert_eq!(&first.buffer[..8], [255, 255, 255, 255, 255, 255, 255, 255]);
+    let second = decoder.read_next_frame().unwrap().unwrap();
+    assert_eq!(&second.buffer[..4], [80, 200, 80, 255]);
+    assert_eq!(&second.buffer[8..12], [0, 0, 0, 255]);
+    assert!(decoder.read_next_frame().unwrap().is_none());
+
+    let path = dir.path().join("out.png");
+    visualise::export(&frames, &path, 1, Duration::from_millis(100)).unwrap();
+    let reader = png::Decoder::new(File::open(&path).unwrap()).read_info().unwrap();
+    assert_eq!((reader.info().width, reader.info().height), (3, 3));
+    assert_eq!(reader.info().animation_control.map(|control| control.num_frames), Some(2));
+
+    let path = dir.path().join("frames");
+    visualise::export(&frames, &path, 1, Duration::from_millis(100)).unwrap();
+    let mut reader = png::Decoder::new(File::open(path.join("frame-00001.png")).unwrap()).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    reader.next_frame(&mut pixels).unwrap();
+    assert_eq!(&pixels[..6], [80, 200, 80, 0, 0, 0]);
+    assert!(!path.join("frame-00002.png").exists());
+
+    assert!(visualise::export(&[], &path, 1, Duration::ZERO).is_err());
+}
@@ -0,0 +1,205 @@
+//! Attributes registering day parsers and part solvers into the registry of
+//! `aoc_common::registry`, see `#[aoc]` and `#[aoc_parser]`.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Error, FnArg, GenericArgument, ItemFn, LitInt, LitStr, Pat, PathArguments, ReturnType, Type};
+
+#[derive(Default)]
+struct Target {
+    day: Option<u32>,
+    part: Option<u32>,
+}
+
+fn parse_target(args: TokenStream, target: &mut Target) -> syn::Result<()> {
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("day") {
+            target.day = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            Ok(())
+        } else if meta.path.is_ident("part") {
+            target.part = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `day` or `part`"))
+        }
+    });
+    syn::parse::Parser::parse(parser, args)
+}
+
+/// `T` of a `-> Result<T>` or `-> T` function.
+fn returned_type(output: &ReturnType) -> Option<Type> {
+    let ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    if !returns_result(output) {
+        return Some(ty.as_ref().clone());
+    }
+    let Type::Path(path) = ty.as_ref() else {
+        return None;
+    };
+    match &path.path.segments.last()?.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn returns_result(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => match ty.as_ref() {
+            Type::Path(path) => path.path.segments.last().is_some_and(|seg| seg.ident == "Result"),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
+
+/// Run the body of `func` inside an info span called `name`, so callers going
+/// through the registry and the day binaries alike show up in traces.
+fn instrument(func: &mut ItemFn, name: String, day: u32) {
+    let name = LitStr::new(&name, Span::call_site());
+    func.block.stmts.insert(0, parse_quote! {
+        let __aoc_span = ::aoc_common::tracing::info_span!(#name, day = #day).entered();
+    });
+}
+
+/// Register a part solver: `#[aoc(day = 4, part = 2)]`.
+///
+/// The function takes the model returned by the day's `#[aoc_parser]`, as
+/// `&Model` or `&[Item]` for a `Vec<Item>` model, and returns an answer that
+/// implements `Display`, optionally wrapped in a `Result`. Its body runs in a
+/// `partN` span.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut target = Target::default();
+    if let Err(err) = parse_target(args, &mut target) {
+        return err.into_compile_error().into();
+    }
+    let mut func = parse_macro_input!(item as ItemFn);
+    let (Some(day), Some(part)) = (target.day, target.part) else {
+        return Error::new(Span::call_site(), "expected `#[aoc(day = N, part = N)]`").into_compile_error().into();
+    };
+    instrument(&mut func, format!("part{}", part), day);
+    let model = match func.sig.inputs.first() {
+        Some(FnArg::Typed(arg)) if func.sig.inputs.len() == 1 => match arg.ty.as_ref() {
+            Type::Reference(reference) => match reference.elem.as_ref() {
+                Type::Slice(slice) => {
+                    let elem = &slice.elem;
+                    quote!(::std::vec::Vec<#elem>)
+                },
+                elem => quote!(#elem),
+            },
+            ty => return Error::new_spanned(ty, "solver must take its model by reference").into_compile_error().into(),
+        },
+        _ => return Error::new_spanned(&func.sig, "solver must take exactly the parsed model").into_compile_error().into(),
+    };
+    let name = &func.sig.ident;
+    let wrapper = format_ident!("__aoc_solve_day{}_part{}", day, part);
+    let answer = if returns_result(&func.sig.output) {
+        quote!(::std::result::Result::Ok(#name(model)?.to_string()))
+    } else {
+        quote!(::std::result::Result::Ok(#name(model).to_string()))
+    };
+    quote! {
+        #func
+
+        #[doc(hidden)]
+        fn #wrapper(model: &::aoc_common::registry::Model) -> ::aoc_common::anyhow::Result<::std::string::String> {
+            let model = model.downcast_ref::<#model>()
+                             .ok_or(::aoc_common::anyhow::anyhow!("Day {} part {}: unexpected model type", #day, #part))?;
+            #answer
+        }
+
+        ::aoc_common::inventory::submit! {
+            ::aoc_common::registry::Solver { day: #day, part: #part, solve: #wrapper }
+        }
+    }.into()
+}
+
+/// Run the body of the parser `func` on its normalised input, see
+/// `aoc_common::normalise`, pointing the diagnostics it returns back into the
+/// original input.
+fn normalise(func: &mut ItemFn) -> syn::Result<()> {
+    let input = match func.sig.inputs.first() {
+        Some(FnArg::Typed(arg)) if func.sig.inputs.len() == 1 => match arg.pat.as_ref() {
+            Pat::Ident(pat) => pat.ident.clone(),
+            pat => return Err(Error::new_spanned(pat, "parser input must be a plain identifier")),
+        },
+        _ => return Err(Error::new_spanned(&func.sig, "parser must take exactly the input")),
+    };
+    let output = &func.sig.output;
+    let block = &func.block;
+    let locate = match returns_result(output) {
+        true => quote!(.map_err(|err| __aoc_normalised.locate(err))),
+        false => quote!(),
+    };
+    *func.block = parse_quote!({
+        let __aoc_normalised = ::aoc_common::normalise::normalise_logged(#input);
+        (|#input: &str| #output #block)(&__aoc_normalised.text)#locate
+    });
+    Ok(())
+}
+
+/// Register the input parser of a day: `#[aoc_parser(day = 4)]`.
+///
+/// The function takes the puzzle input as `&str` and returns the model the
+/// day's solvers work on, optionally wrapped in a `Result`. Its body runs in
+/// a `parse` span, on the input normalised by `aoc_common::normalise`, and
+/// the diagnostics it returns point into the input as given. The model must
+/// implement `Serialize` and `Deserialize`, for `registry::dump` and
+/// `registry::load`.
+#[proc_macro_attribute]
+pub fn aoc_parser(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut target = Target::default();
+    if let Err(err) = parse_target(args, &mut target) {
+        return err.into_compile_error().into();
+    }
+    let mut func = parse_macro_input!(item as ItemFn);
+    let (Some(day), None) = (target.day, target.part) else {
+        return Error::new(Span::call_site(), "expected `#[aoc_parser(day = N)]`").into_compile_error().into();
+    };
+    if let Err(err) = normalise(&mut func) {
+        return err.into_compile_error().into();
+    }
+    instrument(&mut func, "parse".to_string(), day);
+    let Some(model_type) = returned_type(&func.sig.output) else {
+        return Error::new_spanned(&func.sig, "parser must return its model").into_compile_error().into();
+    };
+    let name = &func.sig.ident;
+    let wrapper = format_ident!("__aoc_parse_day{}", day);
+    let dump = format_ident!("__aoc_dump_day{}", day);
+    let load = format_ident!("__aoc_load_day{}", day);
+    let model = if returns_result(&func.sig.output) {
+        quote!(#name(input)?)
+    } else {
+        quote!(#name(input))
+    };
+    quote! {
+        #func
+
+        #[doc(hidden)]
+        fn #wrapper(input: &str) -> ::aoc_common::anyhow::Result<::aoc_common::registry::Model> {
+            ::std::result::Result::Ok(::std::boxed::Box::new(#model))
+        }
+
+        #[doc(hidden)]
+        fn #dump(model: &::aoc_common::registry::Model) -> ::aoc_common::anyhow::Result<::aoc_common::serde_json::Value> {
+            let model = model.downcast_ref::<#model_type>()
+                             .ok_or(::aoc_common::anyhow::anyhow!("Day {}: unexpected model type", #day))?;
+            ::std::result::Result::Ok(::aoc_common::serde_json::to_value(model)?)
+        }
+
+        #[doc(hidden)]
+        fn #load(value: ::aoc_common::serde_json::Value) -> ::aoc_common::anyhow::Result<::aoc_common::registry::Model> {
+            let model: #model_type = ::aoc_common::serde_json::from_value(value)?;
+            ::std::result::Result::Ok(::std::boxed::Box::new(model))
+        }
+
+        ::aoc_common::inventory::submit! {
+            ::aoc_common::registry::Parser { day: #day, parse: #wrapper, dump: #dump, load: #load }
+        }
+    }.into()
+}
@@ -0,0 +1,63 @@
+//! Parse and part timings of every registered day on its real input.
+//!
+//! `cargo bench -p aoc -- --save-baseline <name>` records a baseline, and
+//! `aoc bench-report --baseline <name>` compares the latest run against it.
+
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::Duration;
+use criterion::{criterion_group, criterion_main, Criterion};
+use aoc_common::registry;
+
+// Link every day so their solvers get registered.
+use day_1 as _;
+use day_2 as _;
+use day_3 as _;
+use day_4 as _;
+use day_5 as _;
+use day_6 as _;
+use day_7 as _;
+
+fn solvers(c: &mut Criterion) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    for day in registry::days() {
+        let input = match fs::read_to_string(root.join(format!("day-{}", day)).join("input")) {
+            Ok(input) if !input.is_empty() => input,
+            Ok(_) => {
+                eprintln!("Day {}: empty input, skipping", day);
+                continue;
+            },
+            Err(_) => {
+                eprintln!("Day {}: no input, skipping", day);
+                continue;
+            },
+        };
+        let model = match registry::parse(day, &input) {
+            Ok(model) => model,
+            Err(err) => {
+                eprintln!("Day {}: parse failed, skipping: {:#}", day, err);
+                continue;
+            },
+        };
+        let mut group = c.benchmark_group(format!("day-{}", day));
+        group.bench_function("parse", |b| b.iter(|| registry::parse(day, black_box(&input))));
+        for part in registry::parts(day) {
+            if let Err(err) = registry::solve_model(day, part, &model) {
+                eprintln!("Day {} part {}: failed, skipping: {:#}", day, part, err);
+                continue;
+            }
+            group.bench_function(format!("part{}", part), |b| {
+                b.iter(|| registry::solve_model(day, part, black_box(&model)))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20).measurement_time(Duration::from_secs(2));
+    targets = solvers
+}
+criterion_main!(benches);
@@ -0,0 +1,74 @@
+//! Summary of the criterion results of `cargo bench -p aoc`, optionally
+//! compared against a baseline saved with `--save-baseline`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+#[derive(Deserialize)]
+struct Estimates {
+    mean: Estimate,
+}
+
+/// Timing of one benchmark, e.g. `day-5/part2`.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub id: String,
+    pub mean_ns: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline_ns: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_pct: Option<f64>,
+    pub regressed: bool,
+}
+
+fn mean(dir: &Path) -> Result<Option<f64>> {
+    let path = dir.join("estimates.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)?;
+    let estimates: Estimates = serde_json::from_str(&content).with_context(|| format!("Invalid {}", path.display()))?;
+    Ok(Some(estimates.mean.point_estimate))
+}
+
+fn subdirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)?.filter_map(|entry| entry.ok())
+                                                  .map(|entry| entry.path())
+                                                  .filter(|path| path.is_dir())
+                                                  .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Collect the latest results under `criterion_dir` (`target/criterion`),
+/// flagging the benchmarks more than `threshold` percent slower than
+/// `baseline`.
+pub fn report(criterion_dir: &Path, baseline: Option<&str>, threshold: f64) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+    let groups = subdirs(criterion_dir).with_context(|| format!("No benchmark results in {}", criterion_dir.display()))?;
+    for group in groups.iter().filter(|group| group.file_name().is_some_and(|name| name != "report")) {
+        for bench in subdirs(group)? {
+            let Some(mean_ns) = mean(&bench.join("new"))? else { continue };
+            let baseline_ns = match baseline {
+                Some(name) => mean(&bench.join(name))?,
+                None => None,
+            };
+            let change_pct = baseline_ns.map(|base| (mean_ns - base) / base * 100.0);
+            records.push(Record {
+                id: format!("{}/{}", group.file_name().unwrap().to_string_lossy(), bench.file_name().unwrap().to_string_lossy()),
+                mean_ns,
+                baseline_ns,
+                change_pct,
+                regressed: change_pct.is_some_and(|change| change > threshold),
+            });
+        }
+    }
+    Ok(records)
+}
@@ -0,0 +1,328 @@
+mod bench;
+mod scaffold;
+mod serve;
+mod tui;
+mod watch;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use anyhow::{anyhow, bail, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use aoc_common::input::{self, InputArgs, Provider};
+use aoc_common::diagnostic::in_file;
+use aoc_common::submit::Submitter;
+use aoc_common::trace::TraceArgs;
+use aoc_common::visualise::VisualiseArgs;
+use aoc_common::output::{self, Format};
+use aoc_common::{examples, registry, PartArgs};
+
+// Link every day so their solvers get registered.
+use day_1 as _;
+use day_2 as _;
+use day_3 as _;
+use day_4 as _;
+use day_5 as _;
+use day_6 as _;
+use day_7 as _;
+
+/// Counts the allocations of every parse and part, see `aoc_common::alloc`.
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOC: aoc_common::alloc::Counting = aoc_common::alloc::Counting;
+
+/// Advent of Code 2023 runner for every registered day
+#[derive(Parser, Debug)]
+#[command(version)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+    #[command(flatten)]
+    trace: TraceArgs,
+}
+
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    /// Day to solve, all registered days when missing
+    #[arg(short, long)]
+    day: Option<u32>,
+    #[command(flatten)]
+    part: PartArgs,
+    #[command(flatten)]
+    input: InputArgs,
+    /// Directory holding the `day-N` directories
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+    /// Solve the examples of `puzzle.md` and `examples.toml` first
+    #[arg(long)]
+    examples: bool,
+    /// Read the inputs as JSON models, as printed by `aoc dump`, instead of
+    /// parsing them
+    #[arg(long)]
+    models: bool,
+    /// Print one record per part, as JSON lines or CSV with timings and the
+    /// input hash
+    #[arg(short, long, value_enum, default_value = "text")]
+    output: Format,
+    #[command(flatten)]
+    visualise: VisualiseArgs,
+}
+
+/// Output of `aoc dump`.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum DumpFormat {
+    Json,
+    Yaml,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve a day, or every registered day, on its `day-N/input` or the
+    /// cached/downloaded input
+    Run(RunArgs),
+    /// Print the model parsed from the input of a day, to inspect or diff it
+    Dump {
+        /// Day to parse
+        #[arg(short, long)]
+        day: u32,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Directory holding the `day-N` directories
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+        #[arg(short, long, value_enum, default_value = "json")]
+        format: DumpFormat,
+    },
+    /// Answer `POST /v1/{day}/{part}` requests, the input as body, with the
+    /// JSON record of the part
+    Serve {
+        /// Address to listen on, port 0 picking a free one
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        addr: String,
+        /// Requests handled at once
+        #[arg(long, default_value_t = 4)]
+        threads: usize,
+        /// Largest input accepted, in bytes
+        #[arg(long, value_name = "BYTES", default_value_t = 1 << 20)]
+        max_body: u64,
+        /// Time given to each solve before answering 504, in milliseconds
+        #[arg(long, value_name = "MS", default_value_t = 10_000)]
+        timeout: u64,
+        /// Solves running at once, timed out ones included, before answering
+        /// 503
+        #[arg(long, value_name = "N", default_value_t = 8)]
+        max_solves: usize,
+    },
+    /// Browse the days and their puzzles, run parts and follow their logs
+    Tui {
+        /// Directory holding the `day-N` directories
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Rebuild and rerun a day, examples first, whenever its sources, input or
+    /// examples change
+    Watch {
+        /// Day to watch
+        #[arg(short, long)]
+        day: u32,
+        #[command(flatten)]
+        part: PartArgs,
+        /// Workspace directory
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// List the registered days and parts
+    List,
+    /// Solve a part and submit its answer, unless the history already knows it
+    Submit {
+        /// Day to submit
+        #[arg(short, long)]
+        day: u32,
+        /// Part to submit
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+        /// Answer to submit instead of the computed one
+        #[arg(long)]
+        answer: Option<String>,
+        /// Sleep through the cooldown of a previous submission instead of failing
+        #[arg(long)]
+        wait: bool,
+        /// Directory holding the `day-N` directories
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Create and register the `day-N` crate of a new day, with its input
+    /// when it can be downloaded
+    New {
+        /// Day to create
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+        /// Workspace directory
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Write the examples of every registered day as the seed corpus of its
+    /// fuzz target
+    Corpus {
+        /// Workspace directory
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+        /// Corpus directory, holding one `day_N` directory per target
+        #[arg(long, default_value = "fuzz/corpus")]
+        out: PathBuf,
+    },
+    /// Summarise the results of `cargo bench -p aoc` as JSON
+    BenchReport {
+        /// Criterion output directory
+        #[arg(long, default_value = "target/criterion")]
+        criterion_dir: PathBuf,
+        /// Baseline saved with `cargo bench -p aoc -- --save-baseline <NAME>`
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Slowdown in percent above which a benchmark counts as regressed
+        #[arg(long, default_value_t = 5.0)]
+        threshold: f64,
+        /// Write the JSON report to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Solve the examples of `day` found in `day_dir`, with their expected answers.
+fn run_examples(day: u32, parts: &[u32], day_dir: &Path, format: Format) -> Result<()> {
+    for example in examples::load(day_dir)?.into_iter().filter(|example| parts.contains(&example.part)) {
+        let records = output::solve(day, &[example.part], &example.input).map_err(|err| in_file(err, "example"))?;
+        for mut record in records {
+            record.expected = Some(example.answer.clone());
+            println!("{}", record.render(format));
+        }
+    }
+    Ok(())
+}
+
+/// Solve `days` on their inputs, after their examples with `--examples`;
+/// with `keep_going` a day without input or failing to solve, like a freshly
+/// scaffolded one, is reported and skipped. With `--visualise` the frames
+/// pushed while solving the inputs are exported.
+fn run(args: &RunArgs, days: Vec<u32>, keep_going: bool) -> Result<()> {
+    if let Some(header) = args.output.header() {
+        println!("{}", header);
+    }
+    let parts = args.part.parts();
+    let solve = match args.models {
+        true => output::solve_json,
+        false => output::solve,
+    };
+    for day in days {
+        let day_dir = args.root.join(format!("day-{}", day));
+        let provider = Provider::from_env().with_local_dir(&day_dir);
+        let examples = match args.examples {
+            true => run_examples(day, &parts, &day_dir, args.output),
+            false => Ok(()),
+        };
+        let solved = examples.and_then(|_| args.input.load(|| provider.input(day))).and_then(|inputs| args.visualise.record(|| {
+            for input_file in inputs {
+                let records = solve(day, &parts, &input_file.text).map_err(|err| in_file(err, &input_file.name))?;
+                for mut record in records {
+                    record.input = args.input.explicit().then(|| input_file.name.clone());
+                    println!("{}", record.render(args.output));
+                }
+            }
+            Ok(())
+        }));
+        match solved {
+            Err(err) if keep_going => eprintln!("Day {}: {}, skipping", day, err),
+            solved => solved?,
+        }
+    }
+    Ok(())
+}
+
+/// Print the models parsed from the inputs of `day`.
+fn dump(day: u32, input: &InputArgs, root: &Path, format: DumpFormat) -> Result<()> {
+    let provider = Provider::from_env().with_local_dir(root.join(format!("day-{}", day)));
+    for input_file in input.load(|| provider.input(day))? {
+        let model = input_file.parse(|text| registry::parse(day, text))?;
+        let value = registry::dump(day, &model)?;
+        match format {
+            DumpFormat::Json => println!("{}", serde_json::to_string_pretty(&value)?),
+            DumpFormat::Yaml => print!("---\n{}", serde_yaml::to_string(&value)?),
+        }
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let _trace = args.trace.init()?;
+    match args.command {
+        Command::Run(run_args) => {
+            match run_args.day {
+                Some(day) => run(&run_args, vec![day], false),
+                None if run_args.input.explicit() => bail!("--input needs --day"),
+                None if run_args.visualise.visualise.is_some() => bail!("--visualise needs --day"),
+                None => run(&run_args, registry::days(), true),
+            }
+        },
+        Command::Dump { day, input, root, format } => dump(day, &input, &root, format),
+        Command::Serve { addr, threads, max_body, timeout, max_solves } => {
+            serve::serve(&addr, threads, serve::Limits { max_body, timeout: Duration::from_millis(timeout), max_solves })
+        },
+        Command::Watch { day, part, root } => watch::watch(&root, day, part.part),
+        Command::Tui { root } => tui::tui(&root, args.trace.log.clone().unwrap_or("info".to_string())),
+        Command::List => {
+            for day in registry::days() {
+                println!("Day {}: parts {:?}", day, registry::parts(day));
+            }
+            Ok(())
+        },
+        Command::Submit { day, part, answer, wait, root } => {
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    let input = Provider::from_env().with_local_dir(root.join(format!("day-{}", day))).input(day)?;
+                    registry::solve(day, part, &input)?
+                },
+            };
+            let session = input::session_from_env().ok_or(anyhow!("No session set (AOC_SESSION)"))?;
+            let history = input::cache_dir_from_env().join(input::YEAR.to_string()).join("submissions.jsonl");
+            let submitter = Submitter::new(session, history);
+            let left = submitter.cooldown_left()?;
+            if wait && left > 0 {
+                eprintln!("Waiting {}s for the cooldown", left);
+                thread::sleep(Duration::from_secs(left));
+            }
+            println!("Day {} part {}: {} -> {:?}", day, part, answer, submitter.submit(day, part, &answer)?);
+            Ok(())
+        },
+        Command::New { day, root } => {
+            let day_dir = scaffold::create(&root, day, &Provider::from_env())?;
+            println!("Created {}", day_dir.display());
+            Ok(())
+        },
+        Command::Corpus { root, out } => {
+            for day in registry::days() {
+                let dir = out.join(format!("day_{}", day));
+                fs::create_dir_all(&dir)?;
+                for example in examples::load(root.join(format!("day-{}", day)))? {
+                    fs::write(dir.join(format!("example-part{}", example.part)), example.input)?;
+                }
+            }
+            Ok(())
+        },
+        Command::BenchReport { criterion_dir, baseline, threshold, output } => {
+            let records = bench::report(&criterion_dir, baseline.as_deref(), threshold)?;
+            let json = serde_json::to_string_pretty(&records)?;
+            match output {
+                Some(path) => fs::write(path, json + "\n")?,
+                None => println!("{}", json),
+            }
+            let regressed: Vec<&str> = records.iter().filter(|record| record.regressed).map(|record| record.id.as_str()).collect();
+            if !regressed.is_empty() {
+                bail!("Regressed beyond {}%: {}", threshold, regressed.join(", "));
+            }
+            Ok(())
+        },
+    }
+}
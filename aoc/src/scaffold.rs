@@ -0,0 +1,107 @@
+//! `aoc new`: a `day-N` crate from the templates in `templates/day`, linked
+//! into the workspace, the runner, its benchmarks and the fuzz targets.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context, Result};
+use aoc_common::input::Provider;
+
+const TEMPLATES: [(&str, &str); 6] = [
+    ("Cargo.toml", include_str!("../templates/day/Cargo.toml.in")),
+    ("src/lib.rs", include_str!("../templates/day/lib.rs.in")),
+    ("src/main.rs", include_str!("../templates/day/main.rs.in")),
+    ("tests/examples.rs", include_str!("../templates/day/examples.rs.in")),
+    ("tests/answers.rs", include_str!("../templates/day/answers.rs.in")),
+    ("puzzle.md", include_str!("../templates/day/puzzle.md.in")),
+];
+
+const FUZZ_TARGET: &str = include_str!("../templates/day/fuzz_target.rs.in");
+const FUZZ_BIN: &str = "
+[[bin]]
+name = \"day_{{day}}\"
+path = \"fuzz_targets/day_{{day}}.rs\"
+test = false
+doc = false
+bench = false
+";
+
+/// A line naming a day, e.g. `use day_7 as _;` for the prefix `use day_` and
+/// the suffix ` as _;`, and what else to add at the end of the file.
+struct Registration {
+    file: &'static str,
+    prefix: &'static str,
+    suffix: &'static str,
+    append: &'static str,
+}
+
+const REGISTRATIONS: [Registration; 5] = [
+    Registration { file: "Cargo.toml", prefix: "\"day-", suffix: "\",", append: "" },
+    Registration { file: "aoc/Cargo.toml", prefix: "day-", suffix: " = ", append: "" },
+    Registration { file: "aoc/src/main.rs", prefix: "use day_", suffix: " as _;", append: "" },
+    Registration { file: "aoc/benches/solvers.rs", prefix: "use day_", suffix: " as _;", append: "" },
+    Registration { file: "fuzz/Cargo.toml", prefix: "day-", suffix: " = ", append: FUZZ_BIN },
+];
+
+fn render(template: &str, day: u32) -> String {
+    template.replace("{{day}}", &day.to_string())
+}
+
+impl Registration {
+    fn day_of(&self, line: &str) -> Option<u32> {
+        let rest = line.trim_start().strip_prefix(self.prefix)?;
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        rest[digits..].starts_with(self.suffix).then(|| rest[..digits].parse().ok()).flatten()
+    }
+
+    /// `text` with a line for `day` among the other days, copying the
+    /// indentation and the rest of the line from a registered day.
+    fn insert(&self, text: &str, day: u32) -> Result<String> {
+        let lines: Vec<&str> = text.lines().collect();
+        let registered: Vec<(usize, u32)> = lines.iter().enumerate()
+                                                 .filter_map(|(i, line)| self.day_of(line).map(|d| (i, d)))
+                                                 .collect();
+        let Some(&(template, other)) = registered.last() else {
+            bail!("No day is registered in {}", self.file);
+        };
+        if registered.iter().any(|&(_, d)| d == day) {
+            bail!("Day {} is already registered in {}", day, self.file);
+        }
+        let at = registered.iter().find(|&&(_, d)| d > day).map(|&(i, _)| i).unwrap_or(template + 1);
+        let line = lines[template].replace(&format!("day-{}", other), &format!("day-{}", day))
+                                  .replace(&format!("day_{}", other), &format!("day_{}", day));
+        let mut lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        lines.insert(at, line);
+        Ok(lines.join("\n") + "\n" + &render(self.append, day))
+    }
+}
+
+/// Create `day-N` under the workspace `root` and register it. Nothing is
+/// written when the day already exists or a registration can't be made.
+pub fn create(root: &Path, day: u32, provider: &Provider) -> Result<PathBuf> {
+    let day_dir = root.join(format!("day-{}", day));
+    if day_dir.exists() {
+        bail!("{} already exists", day_dir.display());
+    }
+    let mut updates = Vec::new();
+    for registration in &REGISTRATIONS {
+        let path = root.join(registration.file);
+        let text = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        updates.push((path, registration.insert(&text, day)?));
+    }
+
+    for (name, template) in TEMPLATES {
+        let path = day_dir.join(name);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, render(template, day))?;
+    }
+    fs::write(root.join(format!("fuzz/fuzz_targets/day_{}.rs", day)), render(FUZZ_TARGET, day))?;
+    let input = provider.input(day).unwrap_or_else(|err| {
+        eprintln!("Day {}: {}, leaving an empty input", day, err);
+        String::new()
+    });
+    fs::write(day_dir.join("input"), input)?;
+    for (path, text) in updates {
+        fs::write(&path, text).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(day_dir)
+}
@@ -0,0 +1,140 @@
+//! `aoc serve`: the registered solvers over HTTP, for tools that would rather
+//! not shell out.
+//!
+//! `POST /v1/{day}/{part}` with the puzzle input as body answers with the
+//! JSON record of `aoc run --output json`, or `{"error": ...}` with a 4xx/5xx
+//! status. Each solve runs on its own thread; past the timeout the request
+//! fails with 504 while the thread finishes in the background, since Rust
+//! threads can't be cancelled. Those still count against the solves allowed
+//! at once, past which requests fail with 503 rather than pile up threads.
+
+use std::io::{Read, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use tiny_http::{Header, Method, Request, Response, Server};
+use aoc_common::diagnostic::in_file;
+use aoc_common::output::{self, Record};
+use aoc_common::registry;
+
+/// Bounds on the work a single request can ask for.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Largest accepted body, in bytes
+    pub max_body: u64,
+    pub timeout: Duration,
+    /// Solves running at once, timed out ones included
+    pub max_solves: usize,
+}
+
+/// Solves running, shared by the server threads.
+struct Solves {
+    running: AtomicUsize,
+    max: usize,
+}
+
+/// A running solve, counted until dropped by its thread.
+struct Slot(Arc<Solves>);
+
+impl Solves {
+    fn start(self: &Arc<Self>) -> Option<Slot> {
+        self.running.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| (running < self.max).then_some(running + 1)).ok()?;
+        Some(Slot(Arc::clone(self)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A request that can't be answered, with the status telling why.
+struct Failure {
+    status: u16,
+    message: String,
+}
+
+fn fail(status: u16, message: impl ToString) -> Failure {
+    Failure { status, message: message.to_string() }
+}
+
+/// Day and part of `/v1/{day}/{part}`.
+fn route(url: &str) -> Option<(u32, u32)> {
+    let path = url.split('?').next()?;
+    let (day, part) = path.strip_prefix("/v1/")?.split_once('/')?;
+    Some((day.parse().ok()?, part.parse().ok()?))
+}
+
+fn read_body(request: &mut Request, limits: &Limits) -> Result<String, Failure> {
+    let too_large = || fail(413, format!("Input larger than {} bytes", limits.max_body));
+    if request.body_length().is_some_and(|len| len as u64 > limits.max_body) {
+        return Err(too_large());
+    }
+    let mut body = Vec::new();
+    request.as_reader().take(limits.max_body + 1).read_to_end(&mut body).map_err(|err| fail(400, err))?;
+    if body.len() as u64 > limits.max_body {
+        return Err(too_large());
+    }
+    String::from_utf8(body).map_err(|_| fail(400, "Input is not UTF-8"))
+}
+
+fn answer(request: &mut Request, limits: &Limits, solves: &Arc<Solves>) -> Result<Record, Failure> {
+    let (day, part) = route(request.url()).ok_or_else(|| fail(404, "Expected /v1/{day}/{part}"))?;
+    if *request.method() != Method::Post {
+        return Err(fail(405, "Expected POST"));
+    }
+    if registry::solver(day, part).is_none() {
+        return Err(fail(404, format!("No solver registered for day {} part {}", day, part)));
+    }
+    let input = read_body(request, limits)?;
+    let slot = solves.start().ok_or_else(|| fail(503, format!("Already running {} solves, try again later", limits.max_solves)))?;
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _slot = slot;
+        sender.send(output::solve(day, &[part], &input))
+    });
+    match receiver.recv_timeout(limits.timeout) {
+        Ok(Ok(mut records)) => Ok(records.remove(0)),
+        Ok(Err(err)) => Err(fail(422, in_file(err, "body"))),
+        Err(RecvTimeoutError::Timeout) => Err(fail(504, format!("No answer within {} ms", limits.timeout.as_millis()))),
+        Err(RecvTimeoutError::Disconnected) => Err(fail(500, "Solver panicked")),
+    }
+}
+
+fn handle(mut request: Request, limits: &Limits, solves: &Arc<Solves>) {
+    let (status, body) = match answer(&mut request, limits, solves) {
+        Ok(record) => (200, serde_json::to_string(&record).unwrap()),
+        Err(failure) => (failure.status, serde_json::json!({ "error": failure.message }).to_string()),
+    };
+    let response = Response::from_string(body).with_status_code(status)
+                                              .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    if let Err(err) = request.respond(response) {
+        eprintln!("Failed to respond: {}", err);
+    }
+}
+
+/// Answer requests on `addr` from `threads` threads, forever.
+pub fn serve(addr: &str, threads: usize, limits: Limits) -> Result<()> {
+    let server = Arc::new(Server::http(addr).map_err(|err| anyhow!("Failed to listen on {}: {}", addr, err))?);
+    println!("Listening on http://{}", server.server_addr());
+    std::io::stdout().flush()?;
+    let solves = Arc::new(Solves { running: AtomicUsize::new(0), max: limits.max_solves.max(1) });
+    let workers: Vec<_> = (0..threads.max(1)).map(|_| {
+        let server = Arc::clone(&server);
+        let solves = Arc::clone(&solves);
+        thread::spawn(move || loop {
+            match server.recv() {
+                Ok(request) => handle(request, &limits, &solves),
+                Err(err) => eprintln!("Failed to receive a request: {}", err),
+            }
+        })
+    }).collect();
+    for worker in workers {
+        worker.join().map_err(|_| anyhow!("Server thread panicked"))?;
+    }
+    Ok(())
+}
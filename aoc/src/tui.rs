@@ -0,0 +1,394 @@
+//! `aoc tui`: the registered days with their stars, the answers and timings
+//! of their last run, the solver's logs as they come and the puzzle text.
+//!
+//! Parts run in a child `aoc run --output json`, the way `aoc watch` runs
+//! them, so a slow or panicking solver never freezes the screen; the child's
+//! stderr feeds the log pane. A part has a star once its answer is locked in
+//! `answers.toml` or the website accepted it.
+
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Row, Table, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use aoc_common::output::Record;
+use aoc_common::submit::Submitter;
+use aoc_common::{answers, input, registry, Part};
+
+const TICK: Duration = Duration::from_millis(100);
+/// Lines kept in the log pane.
+const LOG_LINES: usize = 1000;
+const KEYS: &str = " q quit  ↑↓ day  1/2/a run part  PgUp/PgDn puzzle  c clear logs";
+
+/// What the thread running a part reports.
+enum Message {
+    Log(String),
+    Done { day: u32, result: Result<Vec<Record>, String> },
+}
+
+struct Day {
+    day: u32,
+    title: String,
+    stars: [bool; 2],
+    puzzle: Vec<Line<'static>>,
+    records: Vec<Record>,
+    error: Option<String>,
+}
+
+struct App {
+    root: PathBuf,
+    log_filter: String,
+    days: Vec<Day>,
+    list: ListState,
+    logs: VecDeque<String>,
+    running: Option<(u32, Part)>,
+    scroll: u16,
+    sender: Sender<Message>,
+    receiver: Receiver<Message>,
+}
+
+/// `text` with the markdown escapes, emphasis, code spans and links of the
+/// puzzle pages turned into styled spans.
+fn inline(text: &str, base: Style) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let (mut emphasis, mut code) = (false, false);
+    let style = |emphasis: bool, code: bool| match (emphasis, code) {
+        (_, true) => base.fg(Color::Cyan),
+        (true, false) => base.add_modifier(Modifier::BOLD).fg(Color::Yellow),
+        (false, false) => base,
+    };
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if !code => current.extend(chars.next()),
+            '`' => {
+                spans.push(Span::styled(std::mem::take(&mut current), style(emphasis, code)));
+                code = !code;
+            },
+            '*' if !code => {
+                spans.push(Span::styled(std::mem::take(&mut current), style(emphasis, code)));
+                emphasis = !emphasis;
+            },
+            '[' if !code => {},
+            ']' if !code && chars.peek() == Some(&'(') => {
+                while chars.next().is_some_and(|c| c != ')') {}
+            },
+            c => current.push(c),
+        }
+    }
+    spans.push(Span::styled(current, style(emphasis, code)));
+    Line::from(spans)
+}
+
+/// The lines of `puzzle.md`, headings and code blocks highlighted.
+fn render_puzzle(markdown: &str) -> Vec<Line<'static>> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut rendered = Vec::new();
+    let mut fenced = false;
+    for (i, line) in lines.iter().enumerate() {
+        let underline = |line: &str| !line.is_empty() && line.chars().all(|c| c == '-' || c == '=');
+        if line.trim_start().starts_with("```") {
+            fenced = !fenced;
+        } else if fenced {
+            rendered.push(Line::styled(line.to_string(), Style::new().fg(Color::Cyan)));
+        } else if underline(line) && i > 0 && !lines[i - 1].is_empty() {
+            continue;
+        } else if line.starts_with('#') || lines.get(i + 1).is_some_and(|next| underline(next)) && !line.is_empty() {
+            rendered.push(inline(line.trim_start_matches('#').trim(), Style::new().add_modifier(Modifier::BOLD)));
+        } else {
+            rendered.push(inline(line, Style::new()));
+        }
+    }
+    rendered
+}
+
+/// `Day 5: If You Give A Seed A Fertilizer` from the first line of `puzzle.md`.
+fn title(markdown: &str, day: u32) -> String {
+    let first = markdown.lines().find(|line| !line.trim().is_empty()).unwrap_or_default();
+    let title = first.replace('\\', "").trim_matches(|c: char| c == '-' || c == '#' || c.is_whitespace()).to_string();
+    match title.is_empty() {
+        true => format!("Day {}", day),
+        false => title,
+    }
+}
+
+fn stars(day_dir: &Path, day: u32, submitter: &Submitter) -> [bool; 2] {
+    let locked = answers::load(day_dir).unwrap_or_default();
+    [1, 2].map(|part| locked.contains_key(&format!("part{}", part)) || submitter.solved(day, part).unwrap_or(false))
+}
+
+/// Run `part` of `day` in a child `aoc run`, sending its log lines then its
+/// records.
+fn run_part(root: &Path, log_filter: &str, day: u32, part: Part, sender: Sender<Message>) -> Result<()> {
+    let mut child = Command::new(env::current_exe()?).args(["run", "--output", "json", "--day", &day.to_string()])
+                                                     .args(["--part", part.to_possible_value().unwrap().get_name()])
+                                                     .args(["--log", log_filter, "--root"])
+                                                     .arg(root)
+                                                     .stdin(Stdio::null())
+                                                     .stdout(Stdio::piped())
+                                                     .stderr(Stdio::piped())
+                                                     .spawn()
+                                                     .context("Failed to start aoc run")?;
+    let stderr = child.stderr.take().unwrap();
+    let logs = sender.clone();
+    let tail = thread::spawn(move || {
+        let mut error = None;
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            if let Some(message) = line.strip_prefix("Error: ") {
+                error.get_or_insert(message.to_string());
+            }
+            let _ = logs.send(Message::Log(line));
+        }
+        error
+    });
+    let mut stdout = String::new();
+    child.stdout.take().unwrap().read_to_string(&mut stdout)?;
+    let status = child.wait()?;
+    let error = tail.join().unwrap_or_default();
+    let result = match status.success() {
+        true => stdout.lines().map(|line| serde_json::from_str(line).map_err(|err| err.to_string())).collect(),
+        false => Err(error.unwrap_or(format!("aoc run failed ({})", status))),
+    };
+    let _ = sender.send(Message::Done { day, result });
+    Ok(())
+}
+
+impl App {
+    fn new(root: &Path, log_filter: String) -> Self {
+        let history = input::cache_dir_from_env().join(input::YEAR.to_string()).join("submissions.jsonl");
+        let submitter = Submitter::new(input::session_from_env().unwrap_or_default(), history);
+        let days = registry::days().into_iter().map(|day| {
+            let day_dir = root.join(format!("day-{}", day));
+            let markdown = fs::read_to_string(day_dir.join("puzzle.md")).unwrap_or_default();
+            Day {
+                day,
+                title: title(&markdown, day),
+                stars: stars(&day_dir, day, &submitter),
+                puzzle: render_puzzle(&markdown),
+                records: Vec::new(),
+                error: None,
+            }
+        }).collect();
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            root: root.to_path_buf(),
+            log_filter,
+            days,
+            list: ListState::default().with_selected(Some(0)),
+            logs: VecDeque::new(),
+            running: None,
+            scroll: 0,
+            sender,
+            receiver,
+        }
+    }
+
+    fn selected(&self) -> Option<&Day> {
+        self.days.get(self.list.selected()?)
+    }
+
+    fn log(&mut self, line: String) {
+        if self.logs.len() == LOG_LINES {
+            self.logs.pop_front();
+        }
+        self.logs.push_back(line);
+    }
+
+    fn start(&mut self, part: Part) {
+        let Some(day) = self.selected().map(|day| day.day) else { return };
+        if self.running.is_some() {
+            return;
+        }
+        self.running = Some((day, part));
+        let (root, filter, sender) = (self.root.clone(), self.log_filter.clone(), self.sender.clone());
+        thread::spawn(move || {
+            if let Err(err) = run_part(&root, &filter, day, part, sender.clone()) {
+                let _ = sender.send(Message::Done { day, result: Err(err.to_string()) });
+            }
+        });
+    }
+
+    /// Take in what the running part reported since the last tick.
+    fn receive(&mut self) {
+        while let Ok(message) = self.receiver.try_recv() {
+            match message {
+                Message::Log(line) => self.log(line),
+                Message::Done { day, result } => {
+                    self.running = None;
+                    if let Some(entry) = self.days.iter_mut().find(|entry| entry.day == day) {
+                        match result {
+                            Ok(records) => {
+                                for record in records {
+                                    entry.records.retain(|old| old.part != record.part);
+                                    entry.records.push(record);
+                                }
+                                entry.records.sort_by_key(|record| record.part);
+                                entry.error = None;
+                            },
+                            Err(err) => entry.error = Some(err),
+                        }
+                    }
+                },
+            }
+        }
+    }
+
+    /// Handle a key, false once asked to quit.
+    fn key(&mut self, code: KeyCode) -> bool {
+        let last = self.days.len().saturating_sub(1);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.list.select(self.list.selected().map(|at| at.saturating_sub(1)));
+                self.scroll = 0;
+            },
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.list.select(self.list.selected().map(|at| (at + 1).min(last)));
+                self.scroll = 0;
+            },
+            KeyCode::Char('1') => self.start(Part::One),
+            KeyCode::Char('2') => self.start(Part::Two),
+            KeyCode::Char('a') => self.start(Part::All),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char('c') => self.logs.clear(),
+            _ => {},
+        }
+        true
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [list, middle, puzzle] = Layout::horizontal([Constraint::Length(16), Constraint::Percentage(45), Constraint::Min(0)]).areas(main);
+        let [results, logs] = Layout::vertical([Constraint::Length(8), Constraint::Min(0)]).areas(middle);
+
+        let items: Vec<ListItem> = self.days.iter().map(|day| {
+            let stars: String = day.stars.iter().map(|&star| match star { true => '*', false => '.' }).collect();
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("Day {:<3}", day.day)),
+                Span::styled(stars, Style::new().fg(Color::Yellow)),
+            ]))
+        }).collect();
+        let days = List::new(items).block(Block::bordered().title("Days"))
+                                   .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(days, list, &mut self.list);
+
+        let Some(day) = self.selected() else { return };
+        let mut rows: Vec<Row> = day.records.iter().map(|record| Row::new(vec![
+            record.part.to_string(),
+            record.answer.clone(),
+            format!("{:.3} ms", record.parse_ns as f64 / 1e6),
+            format!("{:.3} ms", record.solve_ns as f64 / 1e6),
+        ])).collect();
+        if let Some(err) = &day.error {
+            rows.push(Row::new(vec!["!".to_string(), err.clone()]).style(Style::new().fg(Color::Red)));
+        }
+        let status = match self.running {
+            Some((running, part)) if running == day.day => format!(" running part {:?}", part),
+            _ => String::new(),
+        };
+        let table = Table::new(rows, [Constraint::Length(4), Constraint::Min(16), Constraint::Length(12), Constraint::Length(12)])
+                          .header(Row::new(["Part", "Answer", "Parse", "Solve"]).style(Style::new().add_modifier(Modifier::BOLD)))
+                          .block(Block::bordered().title(format!("{}{}", day.title, status)));
+        frame.render_widget(table, results);
+
+        let height = logs.height.saturating_sub(2) as usize;
+        let tail: Vec<Line> = self.logs.iter().skip(self.logs.len().saturating_sub(height)).map(|line| Line::raw(line.as_str())).collect();
+        frame.render_widget(Paragraph::new(tail).block(Block::bordered().title(format!("Log ({})", self.log_filter))), logs);
+
+        let text = Paragraph::new(day.puzzle.clone()).wrap(Wrap { trim: false })
+                                                     .scroll((self.scroll, 0))
+                                                     .block(Block::bordered().title("puzzle.md"));
+        frame.render_widget(text, puzzle);
+        frame.render_widget(Line::styled(KEYS, Style::new().add_modifier(Modifier::DIM)), footer);
+    }
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    loop {
+        app.receive();
+        terminal.draw(|frame| app.draw(frame))?;
+        if !event::poll(TICK)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !app.key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Run the interface until `q`, solving the days of the workspace at `root`
+/// with their logs filtered by `log_filter`.
+pub fn tui(root: &Path, log_filter: String) -> Result<()> {
+    let mut app = App::new(root, log_filter);
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The text of the non-empty spans of `line`, with their style patched
+    /// onto the line's.
+    fn spans(line: &Line) -> Vec<(String, Style)> {
+        line.spans.iter().filter(|span| !span.content.is_empty())
+                  .map(|span| (span.content.to_string(), line.style.patch(span.style)))
+                  .collect()
+    }
+
+    #[test]
+    fn styles_inline_markdown() {
+        let base = Style::new().add_modifier(Modifier::BOLD);
+        let line = inline("a \\*b\\* `c*d` *gold* [link](https://adventofcode.com) e", Style::new());
+        assert_eq!(spans(&line), [
+            ("a *b* ".to_string(), Style::new()),
+            ("c*d".to_string(), Style::new().fg(Color::Cyan)),
+            (" ".to_string(), Style::new()),
+            ("gold".to_string(), Style::new().add_modifier(Modifier::BOLD).fg(Color::Yellow)),
+            (" link e".to_string(), Style::new()),
+        ]);
+        assert_eq!(spans(&inline("`x`", base)), [("x".to_string(), base.fg(Color::Cyan))]);
+    }
+
+    #[test]
+    fn renders_headings_and_code_blocks() {
+        let markdown = "Day 1\n=====\n\n## Part Two\n\nSum:\n\n```\n1*2\n```\n";
+        let lines: Vec<_> = render_puzzle(markdown).iter().map(spans).collect();
+        let bold = Style::new().add_modifier(Modifier::BOLD);
+        assert_eq!(lines, [
+            vec![("Day 1".to_string(), bold)],
+            vec![],
+            vec![("Part Two".to_string(), bold)],
+            vec![],
+            vec![("Sum:".to_string(), Style::new())],
+            vec![],
+            vec![("1*2".to_string(), Style::new().fg(Color::Cyan))],
+        ]);
+    }
+
+    #[test]
+    fn titles_days_from_their_first_line() {
+        assert_eq!(title("\n## \\-\\-\\- Day 5: If You Give A Seed A Fertilizer \\-\\-\\-\n", 5), "Day 5: If You Give A Seed A Fertilizer");
+        assert_eq!(title("--- Day 7: Camel Cards ---\n", 7), "Day 7: Camel Cards");
+        assert_eq!(title("", 3), "Day 3");
+        assert_eq!(title("\n#\n", 4), "Day 4");
+    }
+}
@@ -0,0 +1,132 @@
+//! `aoc watch`: rerun a day through `cargo run -p aoc -- run --examples`
+//! whenever its sources, input or examples change, and show how the answers
+//! moved since the previous run.
+//!
+//! Changes are found by polling modification times, which is plenty for a
+//! handful of files and behaves the same on every platform and editor.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use aoc_common::Part;
+
+const POLL: Duration = Duration::from_millis(300);
+/// Files under the day directory that trigger a rerun, besides `src/`.
+const WATCHED: [&str; 4] = ["input", "puzzle.md", "examples.toml", "Cargo.toml"];
+
+/// Modification time of every watched file of `day_dir`.
+fn snapshot(day_dir: &Path) -> BTreeMap<PathBuf, SystemTime> {
+    let mut files: Vec<PathBuf> = WATCHED.iter().map(|name| day_dir.join(name)).collect();
+    let mut dirs = vec![day_dir.join("src")];
+    while let Some(dir) = dirs.pop() {
+        for path in fs::read_dir(&dir).into_iter().flatten().flatten().map(|entry| entry.path()) {
+            match path.is_dir() {
+                true => dirs.push(path),
+                false => files.push(path),
+            }
+        }
+    }
+    files.into_iter()
+         .filter_map(|path| fs::metadata(&path).and_then(|meta| meta.modified()).ok().map(|time| (path, time)))
+         .collect()
+}
+
+/// Answers in the order they were printed, keyed by what precedes them.
+type Answers = Vec<(String, String)>;
+
+/// Rebuild the runner and solve `day`, returning its `Day N part P ...: answer`
+/// lines and whether the run got through. Build and solver errors go straight
+/// to the terminal.
+fn solve(root: &Path, day: u32, part: Part) -> Result<(Answers, bool)> {
+    let cargo = env::var_os("CARGO").unwrap_or("cargo".into());
+    let part = part.to_possible_value().unwrap();
+    let output = Command::new(cargo).args(["run", "--quiet", "--package", "aoc", "--", "run", "--examples"])
+                                    .args(["--day", &day.to_string(), "--part", part.get_name(), "--root", "."])
+                                    .current_dir(root)
+                                    .stderr(Stdio::inherit())
+                                    .output()
+                                    .context("Failed to run cargo")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let answers = stdout.lines()
+                        .filter_map(|line| line.split_once(": "))
+                        .map(|(key, answer)| (key.to_string(), answer.to_string()))
+                        .collect();
+    Ok((answers, output.status.success()))
+}
+
+/// One line per answer of `current`, marked `~` when it changed and `+` when
+/// it is new, then `-` for the answers that are gone if the run was `complete`.
+fn diff(previous: &Answers, current: &Answers, complete: bool) -> Vec<String> {
+    let find = |answers: &Answers, key: &str| answers.iter().find(|(other, _)| other == key).map(|(_, answer)| answer.clone());
+    let mut lines: Vec<String> = current.iter().map(|(key, answer)| {
+        match find(previous, key) {
+            Some(old) if &old == answer => format!("  {}: {}", key, answer),
+            Some(old) => format!("~ {}: {} -> {}", key, old, answer),
+            None => format!("+ {}: {}", key, answer),
+        }
+    }).collect();
+    lines.extend(previous.iter().filter(|(key, _)| complete && find(current, key).is_none())
+                                .map(|(key, answer)| format!("- {}: {}", key, answer)));
+    lines
+}
+
+/// Watch `day` under the workspace `root` until interrupted.
+pub fn watch(root: &Path, day: u32, part: Part) -> Result<()> {
+    let day_dir = root.join(format!("day-{}", day));
+    let mut seen = BTreeMap::new();
+    let mut answers = Vec::new();
+    loop {
+        let files = snapshot(&day_dir);
+        if files != seen {
+            seen = files;
+            eprintln!("[watch] day {}: rebuilding and solving", day);
+            let (mut current, complete) = solve(root, day, part)?;
+            for line in diff(&answers, &current, complete) {
+                println!("{}", line);
+            }
+            if !complete {
+                eprintln!("[watch] day {}: failed, keeping the previous answers of the rest", day);
+                let kept: Answers = answers.into_iter().filter(|(key, _)| current.iter().all(|(other, _)| other != key)).collect();
+                current.extend(kept);
+            }
+            answers = current;
+            eprintln!("[watch] waiting for changes in {}", day_dir.display());
+        }
+        thread::sleep(POLL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(pairs: &[(&str, &str)]) -> Answers {
+        pairs.iter().map(|(key, answer)| (key.to_string(), answer.to_string())).collect()
+    }
+
+    #[test]
+    fn marks_changed_new_and_gone_answers() {
+        let previous = answers(&[("Example part 1", "6440"), ("Part 1", "250"), ("Part 2", "248")]);
+        let current = answers(&[("Example part 1", "6440"), ("Part 1", "251"), ("Example part 2", "5905")]);
+        assert_eq!(diff(&previous, &current, true), [
+            "  Example part 1: 6440",
+            "~ Part 1: 250 -> 251",
+            "+ Example part 2: 5905",
+            "- Part 2: 248",
+        ]);
+    }
+
+    #[test]
+    fn keeps_quiet_about_missing_answers_of_a_failed_run() {
+        let previous = answers(&[("Part 1", "250"), ("Part 2", "248")]);
+        let current = answers(&[("Part 1", "250")]);
+        assert_eq!(diff(&previous, &current, false), ["  Part 1: 250"]);
+        assert_eq!(diff(&Vec::new(), &current, false), ["+ Part 1: 250"]);
+    }
+}
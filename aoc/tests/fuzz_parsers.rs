@@ -0,0 +1,107 @@
+//! Stable stand-in for the libFuzzer targets of `fuzz/`: mutate the seed
+//! corpus and check every day's parser, then its parts on what it accepts,
+//! return instead of panicking.
+
+use std::fs;
+use std::panic;
+use std::path::Path;
+use aoc_common::registry;
+// Link every day so their parsers get registered.
+use day_1 as _;
+use day_2 as _;
+use day_3 as _;
+use day_4 as _;
+use day_5 as _;
+use day_6 as _;
+use day_7 as _;
+
+const MUTANTS: usize = 500;
+/// Pieces spliced into the seeds: separators of the formats, huge numbers and
+/// multi-byte characters.
+const PIECES: [&str; 12] = ["\n", "\n\n", " ", ":", "|", ",", ";", "-", "0", "99999999999999999999999", "é", "J"];
+
+/// Inputs the parsers accept but the parts once panicked on.
+const REGRESSIONS: [(u32, &str); 10] = [
+    (2, "Game 1: 100000 red, 100000 green, 100000 blue\n"),
+    (3, "123456*654321\n"),
+    (4, "Card 4294967295: 1 | 1\n"),
+    (4, "Card 1: 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32 33 \
+        | 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32 33\n"),
+    (5, "seeds: 18446744073709551615\n"),
+    (5, "seeds: 18446744073709551615 10\n"),
+    (5, "seeds: 1\n"),
+    (6, "Time: 1\nDistance: 5\n"),
+    (6, "Time: 4\nDistance: 4\n"),
+    (6, "Time: 99999999999 99999999999\nDistance: 1 1\n"),
+];
+
+/// Parse `input` and solve every part of `day` on it, `false` on a panic.
+fn survives(day: u32, input: &str) -> bool {
+    panic::catch_unwind(|| {
+        if let Ok(model) = registry::parse(day, input) {
+            for part in registry::parts(day) {
+                let _ = registry::solve_model(day, part, &model);
+            }
+        }
+    }).is_ok()
+}
+
+/// xorshift64, deterministic so failures reproduce.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+fn mutate(seed: &str, rng: &mut Rng) -> String {
+    let mut chars: Vec<char> = seed.chars().collect();
+    for _ in 0..=rng.below(4) {
+        let at = rng.below(chars.len() + 1);
+        match rng.below(4) {
+            0 => { chars.splice(at..at, PIECES[rng.below(PIECES.len())].chars()); },
+            1 if at < chars.len() => { chars.remove(at); },
+            2 => chars.truncate(at),
+            _ => {
+                let end = (at + rng.below(16)).min(chars.len());
+                let chunk: Vec<char> = chars[at..end].to_vec();
+                chars.splice(at..at, chunk);
+            },
+        }
+    }
+    chars.into_iter().collect()
+}
+
+#[test]
+fn parsers_and_solvers_never_panic() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fuzz/corpus");
+    let mut rng = Rng(0x2023_1201);
+    let mut panics = Vec::new();
+    panic::set_hook(Box::new(|_| {}));
+    for (day, input) in REGRESSIONS {
+        if !survives(day, input) {
+            panics.push((day, input.to_string()));
+        }
+    }
+    for day in registry::days() {
+        let seeds: Vec<String> = fs::read_dir(corpus.join(format!("day_{}", day))).into_iter()
+                                                                                  .flatten()
+                                                                                  .flatten()
+                                                                                  .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+                                                                                  .collect();
+        for seed in &seeds {
+            for _ in 0..MUTANTS {
+                let input = mutate(seed, &mut rng);
+                if !survives(day, &input) {
+                    panics.push((day, input));
+                }
+            }
+        }
+    }
+    let _ = panic::take_hook();
+    assert!(panics.is_empty(), "{} panics, first on day {}:\n{:?}", panics.len(), panics[0].0, panics[0].1);
+}
@@ -0,0 +1,78 @@
+//! Models dumped as JSON load back into the same answers, and `aoc dump`
+//! output feeds `aoc run --models`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use aoc_common::{examples, registry};
+// Link every day so their parsers get registered.
+use day_1 as _;
+use day_2 as _;
+use day_3 as _;
+use day_4 as _;
+use day_5 as _;
+use day_6 as _;
+use day_7 as _;
+
+#[test]
+fn models_round_trip_through_json() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    for day in registry::days() {
+        for example in examples::load(root.join(format!("day-{}", day))).unwrap() {
+            let value = registry::dump(day, &registry::parse(day, &example.input).unwrap()).unwrap();
+            let model = registry::load(day, value.clone()).unwrap();
+            assert_eq!(registry::dump(day, &model).unwrap(), value, "day {}", day);
+            assert_eq!(registry::solve_model(day, example.part, &model).unwrap(), example.answer, "day {} part {}", day, example.part);
+        }
+    }
+}
+
+#[test]
+fn rejects_inconsistent_models() {
+    let schematic = serde_json::json!({
+        "rows": 1, "columns": 3,
+        "numbers": [{ "row": 0, "column": 0, "len": 2, "val": 12 }],
+        "symbols": [{ "row": 0, "column": 1, "symbol": "*" }],
+    });
+    let err = registry::load(3, schematic).unwrap_err();
+    assert!(err.to_string().contains("overlaps"), "{}", err);
+
+    let err = registry::load(7, serde_json::json!([{ "hand": "XYZ12", "bid": 3 }])).unwrap_err();
+    assert!(err.to_string().contains("Unrecognized card 'X'"), "{}", err);
+    let err = registry::load(5, serde_json::json!({ "seeds": [1], "maps": [["bogus", []]] })).unwrap_err();
+    assert!(err.to_string().contains("Expected the maps in order"), "{}", err);
+    let maps: Vec<_> = ["seed-to-soil map", "soil-to-fertilizer map", "fertilizer-to-water map", "water-to-light map",
+                        "light-to-temperature map", "temperature-to-humidity map", "humidity-to-location map"]
+        .iter().map(|name| serde_json::json!([name, [{ "src": u64::MAX, "dst": 0, "len": 2 }]])).collect();
+    let err = registry::load(5, serde_json::json!({ "seeds": [1], "maps": maps })).unwrap_err();
+    assert!(err.to_string().contains("goes past u64::MAX"), "{}", err);
+    let err = registry::load(5, serde_json::json!({ "seeds": [], "maps": maps })).unwrap_err();
+    assert!(err.to_string().contains("at least one seed"), "{}", err);
+
+    let err = registry::load(6, serde_json::json!({ "times": [7, 15, 30], "dists": [9] })).unwrap_err();
+    assert!(err.to_string().contains("3 times for 1 distances"), "{}", err);
+    let err = registry::load(6, serde_json::json!({ "times": [], "dists": [] })).unwrap_err();
+    assert!(err.to_string().contains("at least one race"), "{}", err);
+}
+
+#[test]
+fn solves_dumped_models() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("input");
+    let model = dir.path().join("model.json");
+    fs::write(&input, "Time:      7  15   30\nDistance:  9  40  200\n").unwrap();
+    let aoc = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_aoc")).args(args).output().unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let json = aoc(&["dump", "--day", "6", "--input", input.to_str().unwrap()]);
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&json).unwrap(), serde_json::json!({ "times": [7, 15, 30], "dists": [9, 40, 200] }));
+    fs::write(&model, json).unwrap();
+    let answers = aoc(&["run", "--day", "6", "--models", "--input", model.to_str().unwrap()]);
+    assert_eq!(answers, format!("Day 6 part 1 ({0}): 288\nDay 6 part 2 ({0}): 71503\n", model.display()));
+
+    let yaml = aoc(&["dump", "--day", "6", "--format", "yaml", "--input", input.to_str().unwrap()]);
+    assert_eq!(yaml, "---\ntimes:\n- 7\n- 15\n- 30\ndists:\n- 9\n- 40\n- 200\n");
+}
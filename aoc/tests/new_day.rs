@@ -0,0 +1,83 @@
+//! `aoc new` on a minimal workspace.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn workspace(root: &Path) {
+    fs::create_dir_all(root.join("aoc/src")).unwrap();
+    fs::create_dir_all(root.join("aoc/benches")).unwrap();
+    fs::create_dir_all(root.join("fuzz/fuzz_targets")).unwrap();
+    fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\n    \"aoc\",\n    \"day-1\",\n    \"day-3\",\n]\n").unwrap();
+    let deps = "[dependencies]\nday-1 = { path = \"../day-1\" }\nday-3 = { path = \"../day-3\" }\n";
+    fs::write(root.join("aoc/Cargo.toml"), deps).unwrap();
+    fs::write(root.join("fuzz/Cargo.toml"), deps).unwrap();
+    let uses = "use day_1 as _;\nuse day_3 as _;\n\nfn main() {}\n";
+    fs::write(root.join("aoc/src/main.rs"), uses).unwrap();
+    fs::write(root.join("aoc/benches/solvers.rs"), uses).unwrap();
+}
+
+fn new_day(root: &Path, day: u32) -> Output {
+    let cache = root.join("cache");
+    Command::new(env!("CARGO_BIN_EXE_aoc")).args(["new", "--day", &day.to_string(), "--root"])
+                                           .arg(root)
+                                           .env_remove("AOC_SESSION")
+                                           .env("XDG_CONFIG_HOME", &cache)
+                                           .env("AOC_CACHE_DIR", &cache)
+                                           .output()
+                                           .unwrap()
+}
+
+fn read(root: &Path, file: &str) -> String {
+    fs::read_to_string(root.join(file)).unwrap()
+}
+
+#[test]
+fn creates_and_registers_the_day() {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    workspace(root);
+
+    let output = new_day(root, 2);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    for file in ["Cargo.toml", "src/lib.rs", "src/main.rs", "tests/examples.rs", "tests/answers.rs", "puzzle.md"] {
+        assert!(root.join("day-2").join(file).exists(), "missing {}", file);
+    }
+    assert_eq!(read(root, "day-2/input"), "");
+    assert!(read(root, "day-2/src/lib.rs").contains("#[aoc_parser(day = 2)]"));
+    assert!(read(root, "Cargo.toml").contains("\"day-1\",\n    \"day-2\",\n    \"day-3\","));
+    assert!(read(root, "aoc/Cargo.toml").contains("day-2 = { path = \"../day-2\" }\nday-3"));
+    assert!(read(root, "fuzz/Cargo.toml").ends_with("day-3 = { path = \"../day-3\" }\n\n[[bin]]\nname = \"day_2\"\n\
+                                                    path = \"fuzz_targets/day_2.rs\"\ntest = false\ndoc = false\nbench = false\n"));
+    assert!(read(root, "fuzz/fuzz_targets/day_2.rs").contains("day_2::parse(input)"));
+    for file in ["aoc/src/main.rs", "aoc/benches/solvers.rs"] {
+        assert!(read(root, file).contains("use day_1 as _;\nuse day_2 as _;\nuse day_3 as _;\n"), "{}", file);
+    }
+}
+
+#[test]
+fn refuses_to_overwrite_a_day() {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    workspace(root);
+    fs::create_dir(root.join("day-3")).unwrap();
+    let before = read(root, "Cargo.toml");
+
+    let output = new_day(root, 3);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already exists"));
+    assert_eq!(read(root, "Cargo.toml"), before);
+}
+
+#[test]
+fn refuses_a_registered_day() {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    workspace(root);
+
+    let output = new_day(root, 1);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already registered"));
+    assert!(!root.join("day-1").exists());
+}
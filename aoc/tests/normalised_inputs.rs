@@ -0,0 +1,34 @@
+//! Every day solves its examples the same once mangled the way editors and
+//! copy-paste do: BOM, CRLF, trailing whitespace and stray blank lines.
+
+use std::path::Path;
+use aoc_common::{examples, registry};
+// Link every day so their parsers get registered.
+use day_1 as _;
+use day_2 as _;
+use day_3 as _;
+use day_4 as _;
+use day_5 as _;
+use day_6 as _;
+use day_7 as _;
+
+fn mangle(input: &str) -> String {
+    let lines: Vec<String> = input.lines()
+                                  .map(|line| match line.is_empty() {
+                                      true => " \r\n\r\n".to_string(),
+                                      false => format!("{} \r\n", line),
+                                  })
+                                  .collect();
+    format!("\u{feff}\r\n{}\n\n", lines.concat())
+}
+
+#[test]
+fn solves_mangled_examples() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    for day in registry::days() {
+        for example in examples::load(root.join(format!("day-{}", day))).unwrap() {
+            let answer = registry::solve(day, example.part, &mangle(&example.input));
+            assert_eq!(answer.ok().as_deref(), Some(example.answer.as_str()), "day {} part {}", day, example.part);
+        }
+    }
+}
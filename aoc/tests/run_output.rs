@@ -0,0 +1,42 @@
+//! `aoc run --output` on the inputs of the workspace.
+
+use std::fs::File;
+use std::path::Path;
+use std::process::Command;
+use aoc_common::output::{input_hash, Record};
+
+#[test]
+fn prints_json_records() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc")).args(["run", "--day", "5", "--examples", "--output", "json", "--root"])
+                                                        .arg(root)
+                                                        .output()
+                                                        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let records: Vec<Record> = String::from_utf8(output.stdout).unwrap()
+                                                               .lines()
+                                                               .map(|line| serde_json::from_str(line).unwrap())
+                                                               .collect();
+    let input = std::fs::read_to_string(root.join("day-5/input")).unwrap();
+    assert_eq!(records.len(), 4);
+    assert_eq!(records.iter().map(|record| record.part).collect::<Vec<_>>(), [1, 2, 1, 2]);
+    assert_eq!(records[0].expected.as_deref(), Some(records[0].answer.as_str()));
+    assert_eq!(records[2].expected, None);
+    assert_eq!(records[2].input_hash, input_hash(&input));
+    assert_eq!(records[2].parse_ns, records[3].parse_ns);
+}
+
+#[test]
+fn solves_each_input_given() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let example = root.join("fuzz/corpus/day_6/example-part1");
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc")).args(["run", "--day", "6", "--part", "1", "--input", "-"])
+                                                        .arg(&example)
+                                                        .stdin(File::open(&example).unwrap())
+                                                        .output()
+                                                        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(),
+               format!("Day 6 part 1 (-): 288\nDay 6 part 1 ({}): 288\n", example.display()));
+}
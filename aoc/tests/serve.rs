@@ -0,0 +1,100 @@
+//! `aoc serve` on localhost.
+
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::Duration;
+use serde_json::Value;
+use aoc_common::output::input_hash;
+
+const RACES: &str = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+/// A running `aoc serve`, killed when dropped.
+struct Served {
+    child: Child,
+    url: String,
+}
+
+impl Drop for Served {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn serve(args: &[&str]) -> Served {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc")).args(["serve", "--addr", "127.0.0.1:0"])
+                                                           .args(args)
+                                                           .stdout(Stdio::piped())
+                                                           .spawn()
+                                                           .unwrap();
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().unwrap()).read_line(&mut line).unwrap();
+    let url = line.trim().strip_prefix("Listening on ").unwrap().to_string();
+    Served { child, url }
+}
+
+fn response(result: Result<ureq::Response, ureq::Error>) -> (u16, Value) {
+    let response = match result {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(err) => panic!("{}", err),
+    };
+    let status = response.status();
+    (status, serde_json::from_str(&response.into_string().unwrap()).unwrap())
+}
+
+fn post(served: &Served, path: &str, body: &str) -> (u16, Value) {
+    response(ureq::post(&format!("{}{}", served.url, path)).send_string(body))
+}
+
+#[test]
+fn answers_with_records() {
+    let served = serve(&[]);
+    let (status, record) = post(&served, "/v1/6/1", RACES);
+    assert_eq!(status, 200, "{}", record);
+    assert_eq!((record["day"].as_u64(), record["part"].as_u64()), (Some(6), Some(1)));
+    assert_eq!(record["answer"], "288");
+    assert_eq!(record["input_hash"].as_str(), Some(input_hash(RACES).as_str()));
+    assert!(record["solve_ns"].is_u64());
+    assert_eq!(post(&served, "/v1/6/2", RACES).1["answer"], "71503");
+}
+
+#[test]
+fn rejects_bad_requests() {
+    let served = serve(&["--max-body", "64"]);
+    assert_eq!(post(&served, "/v1/9/1", RACES).0, 404);
+    assert_eq!(post(&served, "/v1/6/x", RACES).0, 404);
+    assert_eq!(post(&served, "/v2/6/1", RACES).0, 404);
+    assert_eq!(response(ureq::get(&format!("{}/v1/6/1", served.url)).call()).0, 405);
+
+    let (status, error) = post(&served, "/v1/6/1", &RACES.repeat(2));
+    assert_eq!((status, error["error"].as_str()), (413, Some("Input larger than 64 bytes")));
+
+    let (status, error) = post(&served, "/v1/6/1", "Time: 7\nDistance: x\n");
+    assert_eq!(status, 422);
+    assert!(error["error"].as_str().unwrap().contains(" --> body:2:11"), "{}", error);
+}
+
+#[test]
+fn times_out_slow_solves() {
+    let served = serve(&["--timeout", "1"]);
+    let hands: String = (0..50_000).map(|bid| format!("{} {}\n", ["32T3K", "T55J5", "KK677", "KTJJT", "QQQJA"][bid % 5], bid)).collect();
+    let (status, error) = post(&served, "/v1/7/2", &hands);
+    assert_eq!((status, error["error"].as_str()), (504, Some("No answer within 1 ms")));
+}
+
+#[test]
+fn refuses_solves_past_the_limit() {
+    let served = serve(&["--timeout", "1", "--max-solves", "1", "--max-body", "10000000"]);
+    let hands: String = (0..200_000).map(|bid| format!("{} {}\n", ["32T3K", "T55J5", "KK677", "KTJJT", "QQQJA"][bid % 5], bid)).collect();
+    assert_eq!(post(&served, "/v1/7/2", &hands).0, 504);
+    let (status, error) = post(&served, "/v1/6/1", RACES);
+    assert_eq!((status, error["error"].as_str()), (503, Some("Already running 1 solves, try again later")));
+    // The slot frees up once the timed out solve is done.
+    let freed = (0..600).any(|_| {
+        thread::sleep(Duration::from_millis(100));
+        post(&served, "/v1/6/1", RACES).0 == 200
+    });
+    assert!(freed);
+}
@@ -0,0 +1,39 @@
+//! `aoc run --visualise` exports the frames pushed by day 3.
+
+use std::fs::File;
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn exports_the_frames_of_a_run() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let animation = dir.path().join("day-3.png");
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc")).args(["run", "--day", "3", "--input"])
+                                                        .arg(root.join("fuzz/corpus/day_3/example-part1"))
+                                                        .arg("--visualise")
+                                                        .arg(&animation)
+                                                        .args(["--cell-size", "3"])
+                                                        .output()
+                                                        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    // One frame per row scanned, for each part.
+    let reader = png::Decoder::new(File::open(&animation).unwrap()).read_info().unwrap();
+    assert_eq!((reader.info().width, reader.info().height), (30, 30));
+    assert_eq!(reader.info().animation_control.unwrap().num_frames, 20);
+}
+
+#[test]
+fn fails_without_frames() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc")).args(["run", "--day", "6", "--input"])
+                                                        .arg(root.join("fuzz/corpus/day_6/example-part1"))
+                                                        .arg("--visualise")
+                                                        .arg(dir.path().join("day-6.gif"))
+                                                        .output()
+                                                        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pushed no frames"));
+}
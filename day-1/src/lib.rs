@@ -0,0 +1,61 @@
+//! Day 1: Trebuchet?!
+//!
+//! Recover the calibration values of an amended document: the first and last
+//! digit of each line, optionally spelled out with letters.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use aoc_common::{aoc, aoc_parser};
+use aoc_common::parse::{lines, single_block, Span};
+
+const DIGIT_NAMES: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// The calibration document, one entry per line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Document {
+    pub lines: Vec<String>,
+}
+
+/// Parse the calibration document, a single block of lines.
+#[aoc_parser(day = 1)]
+pub fn parse(input: &str) -> Result<Document> {
+    let entries = lines(single_block(Span::new(input))?).map(|line| line.as_str().to_string())
+                                                        .collect();
+    Ok(Document { lines: entries })
+}
+
+/// Digit starting at byte `idx` of `line`, a char boundary.
+fn digit_at(line: &str, idx: usize, spelled: bool) -> Option<u32> {
+    let rest = &line[idx..];
+    if let Some(digit) = rest.chars().next().and_then(|c| c.to_digit(10)) {
+        return Some(digit);
+    }
+    if !spelled {
+        return None;
+    }
+    DIGIT_NAMES.iter()
+               .position(|name| rest.starts_with(name))
+               .map(|pos| pos as u32 + 1)
+}
+
+/// Calibration value of `line`: its first and last digit as a two-digit
+/// number. With `spelled`, digits written as words (`one`..`nine`) count too.
+pub fn calibration(line: &str, spelled: bool) -> Result<u32> {
+    let first = line.char_indices().find_map(|(idx, _)| digit_at(line, idx, spelled))
+                                   .ok_or(anyhow!("Couldn't find digit"))?;
+    let last = line.char_indices().rev().find_map(|(idx, _)| digit_at(line, idx, spelled))
+                                  .ok_or(anyhow!("Couldn't find digit"))?;
+    Ok(first * 10 + last)
+}
+
+/// Sum of the calibration values using numeric digits only.
+#[aoc(day = 1, part = 1)]
+pub fn part1(document: &Document) -> Result<u32> {
+    document.lines.iter().map(|line| calibration(line, false)).sum()
+}
+
+/// Sum of the calibration values with spelled out digits.
+#[aoc(day = 1, part = 2)]
+pub fn part2(document: &Document) -> Result<u32> {
+    document.lines.iter().map(|line| calibration(line, true)).sum()
+}
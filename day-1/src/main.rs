@@ -1,23 +1,37 @@
-use std::fs;
-use anyhow::{anyhow, Result};
+use anyhow::Result;
+use clap::Parser;
+use aoc_common::trace::TraceArgs;
+use aoc_common::input::{self, InputArgs};
+use aoc_common::PartArgs;
+use day_1::{parse, part1, part2};
+
+/// Advent of Code 2023, day 1: Trebuchet?!
+#[derive(Parser, Debug)]
+#[command(version)]
+struct Args {
+    #[command(flatten)]
+    part: PartArgs,
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    trace: TraceArgs,
+}
 
 fn main() -> Result<()> {
-    let input: String = fs::read_to_string("input")?.parse()?;
-    let lines: Vec<&str> = input.split('\n').collect();
-    let mut sum = 0;
-    for line in lines {
-        if line.is_empty() {
-            break;
+    let args = Args::parse();
+    let _trace = args.trace.init()?;
+    let inputs = args.input.load(|| input::load(1))?;
+    for input in &inputs {
+        if inputs.len() > 1 {
+            println!("==> {} <==", input.name);
+        }
+        let document = input.parse(parse)?;
+        for part in args.part.parts() {
+            match part {
+                1 => println!("Part 1: {}", part1(&document)?),
+                _ => println!("Part 2: {}", part2(&document)?),
+            }
         }
-        let first_idx = line.find(|c: char| c.is_ascii_digit())
-                        .ok_or(anyhow!("Couldn't find digit"))?;
-        let last_idx = line.rfind(|c: char| c.is_ascii_digit())
-                       .ok_or(anyhow!("Couldn't find digit"))?;
-        let first = line.chars().nth(first_idx).unwrap().to_digit(10).unwrap();
-        let last = line.chars().nth(last_idx).unwrap().to_digit(10).unwrap();
-        let number = first * 10 + last;
-        sum += number;
     }
-    println!("{}", sum);
     Ok(())
 }
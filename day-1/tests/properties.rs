@@ -0,0 +1,53 @@
+//! Random calibration documents checked against a reference solver that
+//! searches every digit token with `find`/`rfind`.
+
+use proptest::prelude::*;
+use aoc_common::diagnostic::Diagnostic;
+use day_1::{calibration, parse, part1, part2};
+
+const TOKENS: [&str; 18] = ["1", "2", "3", "4", "5", "6", "7", "8", "9",
+                            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// Lines of letters, some of them not ASCII, digits and digit names, each
+/// with at least one digit.
+fn document() -> impl Strategy<Value = Vec<String>> {
+    let line = ("[a-zé1-9]{0,8}", "[1-9]", "[a-zß1-9]{0,8}", prop::sample::select(&TOKENS[..]), "[a-zü]{0,4}")
+        .prop_map(|(head, digit, middle, token, tail)| format!("{}{}{}{}{}", head, digit, middle, token, tail));
+    prop::collection::vec(line, 1..20)
+}
+
+fn reference(line: &str, tokens: &[&str]) -> u32 {
+    let value = |token: &str| TOKENS.iter().position(|other| *other == token).unwrap() as u32 % 9 + 1;
+    let first = tokens.iter().filter_map(|token| line.find(token).map(|at| (at, *token))).min().unwrap().1;
+    let last = tokens.iter().filter_map(|token| line.rfind(token).map(|at| (at, *token))).max().unwrap().1;
+    value(first) * 10 + value(last)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn part1_matches_reference(lines in document()) {
+        let document = parse(&(lines.join("\n") + "\n")).unwrap();
+        prop_assert_eq!(part1(&document).unwrap(), lines.iter().map(|line| reference(line, &TOKENS[..9])).sum::<u32>());
+    }
+
+    #[test]
+    fn part2_matches_reference(lines in document()) {
+        let document = parse(&(lines.join("\n") + "\n")).unwrap();
+        prop_assert_eq!(part2(&document).unwrap(), lines.iter().map(|line| reference(line, &TOKENS)).sum::<u32>());
+    }
+}
+
+#[test]
+fn reads_lines_with_multibyte_characters() {
+    assert_eq!(calibration("é1x", false).unwrap(), 11);
+    assert_eq!(calibration("ñtwo3ü", true).unwrap(), 23);
+}
+
+#[test]
+fn rejects_lines_after_a_blank_line() {
+    let err = parse("1abc2\n\npqr3stu8vwx\n").unwrap_err();
+    let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+    assert_eq!((diagnostic.line, diagnostic.message.as_str()), (3, "unexpected content after a blank line"));
+}
@@ -0,0 +1,127 @@
+//! Day 2: Cube Conundrum
+//!
+//! Games of cubes drawn from a bag: find the games possible with a given bag
+//! and the minimal bag of each game.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use aoc_common::{aoc, aoc_parser};
+use aoc_common::parse::{int, labelled, lines, separated, tokens, Span};
+use aoc_common::diagnostic::Diagnostic;
+
+/// A handful of cubes of a single colour.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cubes {
+    Red   { cnt: u32 },
+    Green { cnt: u32 },
+    Blue  { cnt: u32 },
+}
+
+impl Cubes {
+    /// Parse `<count> <colour>`.
+    pub fn parse(span: Span) -> Result<Cubes, Diagnostic> {
+        let [cnt, color] = tokens(span)?;
+        let cnt = int(cnt)?;
+        match color.as_str() {
+            "red"   => Ok(Cubes::Red{cnt}),
+            "green" => Ok(Cubes::Green{cnt}),
+            "blue"  => Ok(Cubes::Blue{cnt}),
+            _ => Err(color.error(format!("unknown cube colour {:?}", color.as_str())).with_hint("cubes are red, green or blue")),
+        }
+    }
+    /// Whether the handful fits in a bag of 12 red, 13 green and 14 blue cubes.
+    pub fn valid(&self) -> bool {
+        match self {
+            Cubes::Red{cnt}   => *cnt <= 12,
+            Cubes::Green{cnt} => *cnt <= 13,
+            Cubes::Blue{cnt}  => *cnt <= 14,
+        }
+    }
+}
+
+/// One set of cubes revealed from the bag.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Round {
+    pub cubes: Vec<Cubes>,
+}
+
+impl Round {
+    pub fn new(cubes: Vec<Cubes>) -> Self {
+        Self { cubes }
+    }
+    /// Parse the comma separated handfuls of a round.
+    pub fn parse(span: Span) -> Result<Round, Diagnostic> {
+        let cubes = separated(span, ",").map(Cubes::parse)
+                                        .collect::<Result<Vec<Cubes>, _>>()?;
+        Ok(Round::new(cubes))
+    }
+    pub fn valid(&self) -> bool {
+        self.cubes.iter()
+                  .all(|c| c.valid())
+    }
+}
+
+/// A game: its id and the rounds revealed during it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Game {
+    pub id:    u32,
+    pub set:   Vec<Round>,
+}
+
+impl Game {
+    pub fn new(id: u32, set: Vec<Round>) -> Self {
+        Self { id, set }
+    }
+    /// Whether every round of the game is possible.
+    pub fn valid(&self) -> bool {
+        self.set.iter()
+                  .all(|r| r.valid())
+    }
+    /// Product of the minimum number of cubes of each colour for the game,
+    /// `None` when it overflows.
+    pub fn power(&self) -> Option<u64> {
+        let mut red   = 0;
+        let mut green = 0;
+        let mut blue  = 0;
+
+        for round in &self.set {
+            for cube in &round.cubes {
+                match cube {
+                    Cubes::Red{cnt}   => if red   < *cnt { red   = *cnt; },
+                    Cubes::Green{cnt} => if green < *cnt { green = *cnt; },
+                    Cubes::Blue{cnt}  => if blue  < *cnt { blue  = *cnt; },
+                };
+            }
+        }
+        (red as u64).checked_mul(green as u64)?.checked_mul(blue as u64)
+    }
+}
+
+/// Parse the record of games, one `Game <id>: <rounds>` per line.
+#[aoc_parser(day = 2)]
+pub fn parse(input: &str) -> Result<Vec<Game>> {
+    let games = lines(Span::new(input)).map(|line| {
+                                           let (id, rounds) = labelled(line, "Game")?;
+                                           let set = separated(rounds, ";").map(Round::parse).collect::<Result<_, _>>()?;
+                                           Ok(Game::new( id, set ))
+                                       })
+                                       .collect::<Result<_, Diagnostic>>()?;
+    Ok(games)
+}
+
+/// Sum of the ids of the possible games.
+#[aoc(day = 2, part = 1)]
+pub fn part1(games: &[Game]) -> u64 {
+    games.iter()
+         .filter(|game| game.valid())
+         .map(|game| game.id as u64)
+         .sum()
+}
+
+/// Sum of the power of every game.
+#[aoc(day = 2, part = 2)]
+pub fn part2(games: &[Game]) -> Result<u64> {
+    games.iter().try_fold(0u64, |sum, game| game.power().and_then(|power| sum.checked_add(power)))
+                .ok_or(anyhow!("Sum of the powers overflows a u64"))
+}
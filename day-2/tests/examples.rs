@@ -0,0 +1,15 @@
+//! Checks the solver against the worked examples of `puzzle.md`.
+
+use aoc_common::examples;
+// Link the day so its solvers get registered.
+use day_2 as _;
+
+#[test]
+fn part1_example() {
+    examples::check(env!("CARGO_MANIFEST_DIR"), 2, 1);
+}
+
+#[test]
+fn part2_example() {
+    examples::check(env!("CARGO_MANIFEST_DIR"), 2, 2);
+}
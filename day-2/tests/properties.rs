@@ -0,0 +1,53 @@
+//! Random game records checked against a reference solver working on the
+//! generated draws directly.
+
+use proptest::prelude::*;
+use day_2::{parse, part1, part2};
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+const BAG: [u32; 3] = [12, 13, 14];
+
+/// A round: the count drawn of each colour, 0 when not drawn, at least one
+/// colour being drawn.
+type Round = [u32; 3];
+
+fn games() -> impl Strategy<Value = Vec<Vec<Round>>> {
+    let round = [0..20_u32, 0..20_u32, 1..20_u32].prop_shuffle();
+    prop::collection::vec(prop::collection::vec(round, 1..6), 1..12)
+}
+
+fn record(games: &[Vec<Round>]) -> String {
+    games.iter().enumerate().map(|(i, rounds)| {
+        let rounds: Vec<String> = rounds.iter().map(|round| {
+            let cubes: Vec<String> = round.iter().zip(COLORS)
+                                          .filter(|(cnt, _)| **cnt > 0)
+                                          .map(|(cnt, color)| format!("{} {}", cnt, color))
+                                          .collect();
+            cubes.join(", ")
+        }).collect();
+        format!("Game {}: {}\n", i + 1, rounds.join("; "))
+    }).collect()
+}
+
+fn minimal_bag(rounds: &[Round]) -> [u32; 3] {
+    [0, 1, 2].map(|color| rounds.iter().map(|round| round[color]).max().unwrap())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn part1_matches_reference(games in games()) {
+        let expected: u64 = games.iter().enumerate()
+                                 .filter(|(_, rounds)| minimal_bag(rounds).iter().zip(BAG).all(|(cnt, max)| *cnt <= max))
+                                 .map(|(i, _)| i as u64 + 1)
+                                 .sum();
+        prop_assert_eq!(part1(&parse(&record(&games)).unwrap()), expected);
+    }
+
+    #[test]
+    fn part2_matches_reference(games in games()) {
+        let expected: u64 = games.iter().map(|rounds| minimal_bag(rounds).iter().map(|cnt| *cnt as u64).product::<u64>()).sum();
+        prop_assert_eq!(part2(&parse(&record(&games)).unwrap()).unwrap(), expected);
+    }
+}
@@ -0,0 +1,285 @@
+//! Day 3: Gear Ratios
+//!
+//! Scan the engine schematic for part numbers, the numbers adjacent to a
+//! symbol, and for gears, the `*` symbols adjacent to exactly two numbers.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use anyhow::{anyhow, bail, Error, Result};
+use std::iter::zip;
+use serde::{Deserialize, Serialize};
+use aoc_common::{aoc, aoc_parser};
+use aoc_common::parse::{grid, single_block, Span};
+use aoc_common::visualise::{self, Frame, Marker, Rgb};
+
+/// Content of a schematic cell. The digits of a number share one element.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Element {
+    None,
+    Number {val: u32, used: bool},
+    Symbol {symbol: char},
+}
+
+/// The engine schematic, serialized as a `Schematic`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(into = "Schematic", try_from = "Schematic")]
+pub struct Matrix {
+    pub elements: Vec<Vec<Rc<RefCell<Element>>>>,
+    /// Sum of the gear ratios found so far, `None` once it overflows
+    pub gear_ratio: Option<u64>,
+}
+
+/// Deep copy: the clone gets its own cells, still shared between the digits
+/// of a number, so scanning it leaves the original untouched.
+impl Clone for Matrix {
+    fn clone(&self) -> Self {
+        let mut copies: HashMap<*const RefCell<Element>, Rc<RefCell<Element>>> = HashMap::new();
+        let elements = self.elements.iter()
+                           .map(|row| row.iter()
+                                         .map(|element| Rc::clone(copies.entry(Rc::as_ptr(element))
+                                                                        .or_insert_with(|| Rc::new(RefCell::new(*element.borrow())))))
+                                         .collect())
+                           .collect();
+        Self { elements, gear_ratio: self.gear_ratio }
+    }
+}
+
+/// A number of the schematic, `len` cells wide from `row`, `column`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartNumber {
+    pub row: usize,
+    pub column: usize,
+    pub len: usize,
+    pub val: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Symbol {
+    pub row: usize,
+    pub column: usize,
+    pub symbol: char,
+}
+
+/// Plain form of a `Matrix`: its size, its numbers, once each rather than
+/// once per digit, and its symbols. Every other cell is empty.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schematic {
+    pub rows: usize,
+    pub columns: usize,
+    pub numbers: Vec<PartNumber>,
+    pub symbols: Vec<Symbol>,
+}
+
+impl From<Matrix> for Schematic {
+    fn from(matrix: Matrix) -> Self {
+        let mut schematic = Schematic {
+            rows: matrix.elements.len(),
+            columns: matrix.elements.first().map_or(0, Vec::len),
+            numbers: Vec::new(),
+            symbols: Vec::new(),
+        };
+        for (row, cells) in matrix.elements.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                match *cell.borrow() {
+                    Element::Number{..} if column > 0 && Rc::ptr_eq(&cells[column - 1], cell) => {
+                        schematic.numbers.last_mut().unwrap().len += 1;
+                    },
+                    Element::Number{val, ..} => schematic.numbers.push(PartNumber { row, column, len: 1, val }),
+                    Element::Symbol{symbol} => schematic.symbols.push(Symbol { row, column, symbol }),
+                    Element::None => {},
+                }
+            }
+        }
+        schematic
+    }
+}
+
+impl TryFrom<Schematic> for Matrix {
+    type Error = Error;
+
+    /// Rebuild the cells, shared between the digits of each number. Numbers
+    /// and symbols must lie within the schematic without overlapping.
+    fn try_from(schematic: Schematic) -> Result<Matrix> {
+        let mut elements: Vec<Vec<Rc<RefCell<Element>>>> = (0..schematic.rows).map(|_| (0..schematic.columns).map(|_| Rc::new(RefCell::new(Element::None)))
+                                                                                                              .collect())
+                                                                             .collect();
+        let mut place = |row: usize, columns: std::ops::Range<usize>, element: Element| {
+            if columns.is_empty() || row >= schematic.rows || columns.end > schematic.columns {
+                bail!("{:?} at row {}, columns {:?} is outside the schematic", element, row, columns);
+            }
+            let cell = Rc::new(RefCell::new(element));
+            for column in columns {
+                if *elements[row][column].borrow() != Element::None {
+                    bail!("{:?} overlaps another cell at row {}, column {}", element, row, column);
+                }
+                elements[row][column] = Rc::clone(&cell);
+            }
+            Ok(())
+        };
+        for number in &schematic.numbers {
+            place(number.row, number.column..number.column + number.len, Element::Number{val: number.val, used: false})?;
+        }
+        for symbol in &schematic.symbols {
+            place(symbol.row, symbol.column..symbol.column + 1, Element::Symbol{symbol: symbol.symbol})?;
+        }
+        Ok(Matrix { elements, gear_ratio: Some(0) })
+    }
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Matrix {
+    pub fn new() -> Self {
+        Self { elements: Vec::new(), gear_ratio: Some(0) }
+    }
+
+    fn new_row(&mut self, _row: usize, column_size: usize) {
+        self.elements.push(Vec::with_capacity(column_size));
+    }
+
+    fn new_element(&mut self, row: usize, _column: usize, element: &mut Rc<RefCell<Element>>) {
+        self.elements[row].push(Rc::clone(element));
+    }
+
+    fn valid_indices(&self, row: usize, col: usize, row_offset: isize, col_offset: isize) -> Option<(usize, usize)> {
+        let row  = row as isize + row_offset;
+        let col  = col as isize + col_offset;
+        let rows = self.elements.len() as isize;
+        let cols = self.elements[0].len() as isize;
+        if row >= 0 && row < rows && col >= 0 && col < cols {
+            Some((row as usize, col as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Numbers around the symbol at `row`, `col` not yet collected by another
+    /// symbol. A `*` next to exactly two numbers, collected or not, is a gear.
+    fn find_nearby_numbers(&mut self, row: usize, col: usize, element: Element) -> Vec<u32> {
+        let steps = zip( vec![ -1, -1, -1, 0, 1, 1, 1, 0],
+                         vec![ -1, 0, 1, 1, 1, 0, -1, -1]);
+        let mut numbers = vec![];
+        let mut nearby: Vec<(Rc<RefCell<Element>>, u32)> = vec![];
+        if let Element::Symbol{symbol} = element {
+            for (i, j) in steps {
+                if let Some((k, l)) = self.valid_indices(row, col, i, j) {
+                    let neighbour = Rc::clone(&self.elements[k][l]);
+                    let Element::Number{val, used} = *neighbour.borrow() else { continue };
+                    if nearby.iter().any(|(other, _)| Rc::ptr_eq(other, &neighbour)) {
+                        continue;
+                    }
+                    if !used {
+                        *neighbour.borrow_mut() = Element::Number{val, used: true};
+                        numbers.push(val);
+                    }
+                    nearby.push((neighbour, val));
+                }
+            }
+            if symbol == '*' && nearby.len() == 2 {
+                let ratio = nearby.iter().map(|(_, val)| *val as u64).product::<u64>();
+                self.gear_ratio = self.gear_ratio.and_then(|sum| sum.checked_add(ratio));
+            }
+        }
+        numbers
+    }
+
+    /// The schematic while scanning `row`: part numbers found so far in
+    /// green, other numbers in blue, symbols in white, those of `row`
+    /// highlighted and gears crossed.
+    fn frame(&self, row: usize) -> Frame {
+        let columns = self.elements.first().map_or(0, Vec::len);
+        let mut frame = Frame::from_fn(self.elements.len(), columns, |i, j| match *self.elements[i][j].borrow() {
+            Element::None => Rgb::BLACK,
+            Element::Number{used: true, ..} => Rgb::GREEN,
+            Element::Number{used: false, ..} => Rgb::BLUE,
+            Element::Symbol{..} => Rgb::WHITE,
+        });
+        for (j, element) in self.elements[row].iter().enumerate() {
+            if let Element::Symbol{symbol} = *element.borrow() {
+                frame.highlight(row, j);
+                if symbol == '*' {
+                    frame.mark(row, j, Marker::Cross, Rgb::RED);
+                }
+            }
+        }
+        frame
+    }
+
+    /// Collect the part numbers, i.e. the numbers adjacent to a symbol, and
+    /// accumulate the ratios of the gears into `gear_ratio`.
+    pub fn find_numbers(&mut self) -> Vec<u32> {
+        let mut numbers = vec![];
+        let elements: Vec<_> = self.elements
+                        .iter()
+                        .enumerate()
+                        .flat_map(|(i, row)| row.iter()
+                                                .enumerate()
+                                                .map(move |(j, element)| (i, j, Rc::clone(element)))
+                        ).collect();
+        let columns = self.elements.first().map_or(0, Vec::len);
+        for (i, j, element) in elements {
+            self.find_nearby_numbers(i, j, *element.borrow())
+                .iter()
+                .for_each(|x| numbers.push(*x));
+            if j + 1 == columns {
+                visualise::push(|| self.frame(i));
+            }
+        }
+        numbers
+    }
+}
+
+/// Parse the engine schematic, the rectangular grid of a single block of
+/// lines.
+#[aoc_parser(day = 3)]
+pub fn parse(input: &str) -> Result<Matrix> {
+    let block = single_block(Span::new(input))?;
+    let rows = match block.is_empty() {
+        true => Vec::new(),
+        false => grid(block)?,
+    };
+    let mut matrix = Matrix::new();
+    for (i, row) in rows.iter().enumerate() {
+        matrix.new_row(i, row.as_str().len());
+        let mut element = Rc::new(RefCell::new(Element::None));
+        for (j, c) in row.as_str().char_indices() {
+            if let Some(digit) = c.to_digit(10) {
+                let inner_element = *element.borrow();
+                if let Element::Number{val, used} = inner_element {
+                    let new_val = val.checked_mul(10).and_then(|val| val.checked_add(digit))
+                                     .ok_or_else(|| row.slice(j..j + 1).error("number too large").with_hint("numbers should fit in a u32"))?;
+                    *element.borrow_mut() = Element::Number{ val: new_val, used};
+                } else {
+                    element = Rc::new(RefCell::new(Element::Number{val: digit, used: false }));
+                }
+            } else {
+                element = if c=='.' {
+                    Rc::new(RefCell::new(Element::None))
+                } else {
+                    Rc::new(RefCell::new(Element::Symbol{symbol: c}))
+                };
+            }
+            matrix.new_element(i, j, &mut element);
+        }
+    }
+    Ok(matrix)
+}
+
+/// Sum of the part numbers.
+#[aoc(day = 3, part = 1)]
+pub fn part1(matrix: &Matrix) -> u64 {
+    matrix.clone().find_numbers().iter().map(|number| *number as u64).sum()
+}
+
+/// Sum of the gear ratios.
+#[aoc(day = 3, part = 2)]
+pub fn part2(matrix: &Matrix) -> Result<u64> {
+    let mut matrix = matrix.clone();
+    matrix.find_numbers();
+    matrix.gear_ratio.ok_or(anyhow!("Sum of the gear ratios overflows a u64"))
+}
@@ -0,0 +1,90 @@
+//! Random schematics checked against a reference solver that finds the
+//! numbers first, then looks around each of their digits.
+
+use proptest::prelude::*;
+use aoc_common::diagnostic::Diagnostic;
+use day_3::{parse, part1, part2};
+
+/// Rows of equal width, mostly dots with numbers of up to three digits and a
+/// few symbols.
+fn schematic() -> impl Strategy<Value = Vec<Vec<char>>> {
+    let cell = prop_oneof![6 => Just('.'), 3 => prop::char::range('0', '9'), 1 => prop::sample::select(vec!['*', '#', '+', '$'])];
+    let grid = (1..12_usize, 1..12_usize).prop_flat_map(move |(rows, cols)| {
+        prop::collection::vec(prop::collection::vec(cell.clone(), cols), rows)
+    });
+    grid.prop_map(|mut grid| {
+        for row in grid.iter_mut() {
+            for j in 3..row.len() {
+                if row[j - 3..=j].iter().all(|c| c.is_ascii_digit()) {
+                    row[j] = '.';
+                }
+            }
+        }
+        grid
+    })
+}
+
+/// Numbers of the schematic: value and the cells of their digits.
+fn numbers(grid: &[Vec<char>]) -> Vec<(u32, Vec<(usize, usize)>)> {
+    let mut numbers = Vec::new();
+    for (i, row) in grid.iter().enumerate() {
+        let mut j = 0;
+        while j < row.len() {
+            let len = row[j..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if len > 0 {
+                let value = row[j..j + len].iter().collect::<String>().parse().unwrap();
+                numbers.push((value, (j..j + len).map(|col| (i, col)).collect()));
+            }
+            j += len.max(1);
+        }
+    }
+    numbers
+}
+
+fn adjacent(cells: &[(usize, usize)], (row, col): (usize, usize)) -> bool {
+    cells.iter().any(|&(i, j)| i.abs_diff(row) <= 1 && j.abs_diff(col) <= 1)
+}
+
+fn symbols(grid: &[Vec<char>]) -> Vec<((usize, usize), char)> {
+    grid.iter().enumerate()
+        .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, c)| ((i, j), *c)))
+        .filter(|(_, c)| *c != '.' && !c.is_ascii_digit())
+        .collect()
+}
+
+fn render(grid: &[Vec<char>]) -> String {
+    grid.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn part1_matches_reference(grid in schematic()) {
+        let symbols = symbols(&grid);
+        let expected: u64 = numbers(&grid).iter()
+                                          .filter(|(_, cells)| symbols.iter().any(|(at, _)| adjacent(cells, *at)))
+                                          .map(|(value, _)| *value as u64)
+                                          .sum();
+        prop_assert_eq!(part1(&parse(&render(&grid)).unwrap()), expected);
+    }
+
+    #[test]
+    fn part2_matches_reference(grid in schematic()) {
+        let numbers = numbers(&grid);
+        let expected: u64 = symbols(&grid).iter()
+                                          .filter(|(_, symbol)| *symbol == '*')
+                                          .map(|(at, _)| numbers.iter().filter(|(_, cells)| adjacent(cells, *at)).map(|(value, _)| *value).collect::<Vec<_>>())
+                                          .filter(|values| values.len() == 2)
+                                          .map(|values| values[0] as u64 * values[1] as u64)
+                                          .sum();
+        prop_assert_eq!(part2(&parse(&render(&grid)).unwrap()).unwrap(), expected);
+    }
+}
+
+#[test]
+fn rejects_rows_after_a_blank_line() {
+    let err = parse("467..114..\n...*......\n\n..35..633.\n").unwrap_err();
+    let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+    assert_eq!((diagnostic.line, diagnostic.message.as_str()), (4, "unexpected content after a blank line"));
+}
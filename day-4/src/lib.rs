@@ -0,0 +1,94 @@
+//! Day 4: Scratchcards
+//!
+//! Score scratchcards, then count the copies won by playing them.
+
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeSet, BTreeMap};
+use tracing::{debug_span, instrument, trace};
+use serde::{Deserialize, Serialize};
+use aoc_common::{aoc, aoc_parser};
+use aoc_common::parse::{ints, labelled, lines, pair, Span};
+use aoc_common::diagnostic::Diagnostic;
+
+/// A scratchcard: its winning numbers and the numbers we have.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Card {
+    pub id: u32,
+    pub winning: BTreeSet<u32>,
+    pub own: Vec<u32>,
+}
+
+impl Card {
+    pub fn new(id: u32, winning_vec: Vec<u32>, own: Vec<u32>) -> Self {
+        let winning = BTreeSet::from_iter(winning_vec.iter().copied());
+        trace!(id, ?winning, ?own, "create card");
+        Self { id, winning, own }
+    }
+    /// Points of the card: 1 for the first match, doubled for each other one,
+    /// `None` past `u64::MAX`.
+    #[instrument(level = "debug", name = "card", skip_all, fields(id = self.id))]
+    pub fn get_prize(&self) -> Option<u64> {
+        let mut prize: u64 = 0;
+        for number in self.own.iter() {
+            if self.winning.contains(number) {
+                if prize == 0 {
+                    prize = 1;
+                } else {
+                    prize = prize.checked_mul(2)?;
+                }
+            }
+        }
+        trace!(own = ?self.own, winning = ?self.winning, prize, "scored");
+        Some(prize)
+    }
+
+    /// Ids of the cards copied by winning this one, up to `u32::MAX`.
+    pub fn get_new_cards(&self) -> Vec<u32> {
+        let new_cards_cnt = self.own.iter().fold(0, |acc, x| if self.winning.contains(x) {acc + 1} else {acc});
+        (1..=new_cards_cnt).map_while(|offset| self.id.checked_add(offset)).collect()
+    }
+}
+
+/// Parse the pile of cards, one `Card <id>: <winning> | <own>` per line.
+#[aoc_parser(day = 4)]
+pub fn parse(input: &str) -> Result<Vec<Card>> {
+    let cards = lines(Span::new(input)).map(|line| {
+                                           let (id, numbers) = labelled(line, "Card")?;
+                                           let (winning, own) = pair(numbers, '|')?;
+                                           Ok(Card::new(id, ints(winning)?, ints(own)?))
+                                       })
+                                       .collect::<Result<_, Diagnostic>>()?;
+    Ok(cards)
+}
+
+/// Total points of the pile.
+#[aoc(day = 4, part = 1)]
+pub fn part1(cards: &[Card]) -> Result<u64> {
+    cards.iter().try_fold(0u64, |sum, card| card.get_prize().and_then(|prize| sum.checked_add(prize)))
+                .ok_or(anyhow!("Total points overflow a u64"))
+}
+
+/// Number of cards in the pile once every copy has been won.
+#[aoc(day = 4, part = 2)]
+pub fn part2(cards: &[Card]) -> Result<usize> {
+    let mut count = 0;
+    let card_map = BTreeMap::from_iter(cards.iter().map(|card| (card.id, card)));
+    let mut queue: BTreeMap<u32,usize> = cards.iter().map(|card| (card.id, 1_usize)).collect();
+    for card_id in cards.iter().map(|card| card.id) {
+        let _span = debug_span!("card", id = card_id).entered();
+        let cnt = *queue.get(&card_id).ok_or(anyhow!("Did not find {}", card_id))?;
+        let overflow = || anyhow!("Number of cards overflows a usize");
+        count = usize::checked_add(count, cnt).ok_or_else(overflow)?;
+        let card = card_map.get(&card_id).unwrap();
+        let new_cards = card.get_new_cards();
+        trace!(copies = cnt, ?new_cards, "won");
+        for new_card in new_cards {
+            match queue.get_mut(&new_card) {
+                Some(curr) => *curr = curr.checked_add(cnt).ok_or_else(overflow)?,
+                None => { queue.insert(new_card, 1); },
+            }
+        }
+        queue.remove(&card_id);
+    }
+    Ok(count)
+}
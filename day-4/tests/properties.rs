@@ -0,0 +1,69 @@
+//! Random piles of scratchcards checked against a reference solver that
+//! plays every copy one by one.
+
+use std::collections::BTreeSet;
+use proptest::prelude::*;
+use aoc_common::diagnostic::Diagnostic;
+use day_4::{parse, part1, part2};
+
+/// A card: its winning numbers and the numbers we have.
+type Card = (Vec<u32>, Vec<u32>);
+
+/// Cards never win copies past the end of the pile.
+fn pile() -> impl Strategy<Value = Vec<Card>> {
+    let card = (prop::collection::btree_set(1..100_u32, 5), prop::collection::btree_set(1..100_u32, 8), 0..6_usize);
+    prop::collection::vec(card, 1..10).prop_map(|cards| {
+        let len = cards.len();
+        cards.into_iter().enumerate().map(|(i, (winning, others, matches))| {
+            let matches = matches.min(len - i - 1);
+            let mut own: Vec<u32> = winning.iter().copied().take(matches).collect();
+            own.extend(others.difference(&winning).take(8 - matches));
+            (winning.into_iter().collect(), own)
+        }).collect()
+    })
+}
+
+fn render(cards: &[Card]) -> String {
+    let list = |numbers: &[u32]| numbers.iter().map(|number| format!("{:>2}", number)).collect::<Vec<_>>().join(" ");
+    cards.iter().enumerate()
+         .map(|(i, (winning, own))| format!("Card {:>3}: {} | {}\n", i + 1, list(winning), list(own)))
+         .collect()
+}
+
+fn matches((winning, own): &Card) -> usize {
+    let winning: BTreeSet<_> = winning.iter().collect();
+    own.iter().filter(|number| winning.contains(number)).count()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn part1_matches_reference(cards in pile()) {
+        let expected: u64 = cards.iter().map(|card| match matches(card) {
+            0 => 0,
+            n => 2_u64.pow(n as u32 - 1),
+        }).sum();
+        prop_assert_eq!(part1(&parse(&render(&cards)).unwrap()).unwrap(), expected);
+    }
+
+    #[test]
+    fn part2_matches_reference(cards in pile()) {
+        let mut unplayed: Vec<usize> = (0..cards.len()).collect();
+        let mut played = 0;
+        while let Some(card) = unplayed.pop() {
+            played += 1;
+            unplayed.extend(card + 1..=card + matches(&cards[card]));
+        }
+        prop_assert_eq!(part2(&parse(&render(&cards)).unwrap()).unwrap(), played);
+    }
+}
+
+#[test]
+fn locates_errors_in_the_input_as_given() {
+    let input = "\u{feff}\r\n\r\n\r\nCard 1: 1 x | 3\r\n";
+    let err = parse(input).unwrap_err();
+    let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+    assert_eq!((diagnostic.line, diagnostic.column, diagnostic.excerpt.as_str()), (4, 11, "Card 1: 1 x | 3"));
+    assert_eq!(&input[diagnostic.span.clone()], "x");
+}
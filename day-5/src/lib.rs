@@ -0,0 +1,352 @@
+//! Day 5: If You Give A Seed A Fertilizer
+//!
+//! Follow seed ranges through the almanac maps down to their locations,
+//! splitting ranges wherever a map only covers part of them.
+
+use tracing::{debug, debug_span, trace};
+use anyhow::{anyhow, bail, Error, Result};
+use itertools::Itertools;
+use std::fmt::Debug;
+use std::cmp::Ordering;
+use serde::{Deserialize, Serialize};
+use aoc_common::{aoc, aoc_parser};
+use aoc_common::parse::{blocks, int, ints, label, lines, tokens, Span};
+use aoc_common::diagnostic::Diagnostic;
+
+/// A non-empty range of ids, `start..start+len`.
+#[derive(Debug, Clone, Copy)]
+pub struct IdRange {
+    pub start: u64,
+    pub len: u64,
+}
+
+impl PartialEq for IdRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start
+    }
+}
+
+impl Eq for IdRange {}
+
+impl Ord for IdRange {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start.cmp(&other.start)
+    }
+}
+
+impl PartialOrd for IdRange {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl IdRange {
+    /// Range of `len` ids from `start`, `None` when empty.
+    pub fn new(start: u64, len: u64) -> Option<Self> {
+        if len==0 {
+            None
+        } else {
+            Some(Self { start, len})
+        }
+    }
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let start = std::cmp::max(self.start, other.start);
+        let end = std::cmp::min(self.start + self.len, other.start + other.len);
+        if start < end {
+            let len = end - start;
+            let intersect_range = IdRange{start, len};
+            trace!(?self, ?other, intersection = ?intersect_range, "intersect");
+            Some(intersect_range)
+        } else {
+            None
+        }
+    }
+    /// Ids of `self` not in `other`.
+    pub fn sub(&self, other: &Self) -> Set {
+        if let Some(to_sub) = self.intersect(other){
+            let left = IdRange::new(self.start, to_sub.start.saturating_sub(self.start));
+            let right = IdRange::new(to_sub.start+to_sub.len, (self.start + self.len).saturating_sub(to_sub.start + to_sub.len));
+            trace!(?left, ?right, "remained ranges");
+            let new_set = if let (Some(left_range), Some(right_range)) = (left, right) {
+                Set::Split(Box::new(Set::Interval(left_range)), Box::new(Set::Interval(right_range)))
+            } else if let Some(left_range) = left {
+                Set::Interval(left_range)
+            } else if let Some(right_range) = right {
+                Set::Interval(right_range)
+            } else {
+                Set::None
+            };
+            trace!(?self, ?other, difference = ?new_set, "subtract");
+            new_set
+        } else {
+            Set::Interval(*self)
+        }
+
+    }
+}
+
+/// Union of disjoint ranges, left over while mapping a range.
+#[derive(Clone, Debug)]
+pub enum Set {
+    None,
+    Interval(IdRange),
+    Split(Box<Set>, Box<Set>),
+}
+
+impl Set {
+    pub fn new(init: IdRange) -> Self {
+        Set::Interval(init)
+    }
+    /// Remove the ids of `other` from the set.
+    pub fn sub(&mut self, other: IdRange) {
+        let new_set = match self {
+            Set::Interval(id) => id.sub(&other),
+            Set::Split(left, right) => {
+                left.sub(other);
+                right.sub(other);
+                Set::Split(left.clone(), right.clone())
+            },
+            Set::None => Set::None,
+        };
+        *self = new_set;
+    }
+    /// Iterate over the disjoint ranges of the set.
+    pub fn yield_intervals(&self) -> Box<dyn Iterator<Item = IdRange>> {
+        match self {
+            Set::Interval(id) => Box::new(std::iter::once(*id)),
+            Set::Split(left, right) => Box::new(left.yield_intervals().chain(right.yield_intervals())),
+            Set::None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+/// A seed range and the ranges it maps to at each stage of the almanac.
+#[derive(Debug)]
+pub struct Seed {
+    pub seed:       IdRange,
+    pub soil:       Vec<IdRange>,
+    pub fertilizer: Vec<IdRange>,
+    pub water:      Vec<IdRange>,
+    pub light:      Vec<IdRange>,
+    pub temp:       Vec<IdRange>,
+    pub humidity:   Vec<IdRange>,
+    pub location:   Vec<IdRange>,
+}
+
+/// One line of an almanac map: `len` ids from `src` map to `dst` onwards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Map {
+    pub src: u64,
+    pub dst: u64,
+    pub len: u64,
+}
+
+impl Map {
+    /// Whether both ranges of the map fit in `u64`.
+    fn fits(&self) -> bool {
+        self.src.checked_add(self.len).is_some() && self.dst.checked_add(self.len).is_some()
+    }
+
+    /// Part of `src` covered by the map, and where it maps to.
+    pub fn contains(&self, src: IdRange) -> Option<(IdRange, IdRange)> {
+        trace!(?src, map = ?self, "check mapping");
+        let src_range = IdRange{start: self.src, len: self.len};
+        if let Some(intersect) = src_range.intersect(&src) {
+            let offset = intersect.start - self.src;
+            let start = self.dst + offset;
+            let len = intersect.len;
+            let dst_range = IdRange{start, len};
+            trace!(src = ?intersect, dst = ?dst_range, "found mapping");
+            Some((intersect, dst_range))
+        } else {
+            None
+        }
+    }
+}
+
+
+impl Seed {
+    pub fn new(start: u64, len: u64) -> Self {
+        Self {
+            seed: IdRange{start, len},
+            soil:       Vec::new(), // don't care at init
+            fertilizer: Vec::new(), // don't care at init
+            water:      Vec::new(), // don't care at init
+            light:      Vec::new(), // don't care at init
+            temp:       Vec::new(), // don't care at init
+            humidity:   Vec::new(), // don't care at init
+            location:   Vec::new(), // don't care at init
+        }
+    }
+}
+
+/// Names of the maps, in the order the seeds go through them.
+const MAP_NAMES: [&str; 7] = ["seed-to-soil map", "soil-to-fertilizer map", "fertilizer-to-water map", "water-to-light map",
+                              "light-to-temperature map", "temperature-to-humidity map", "humidity-to-location map"];
+
+/// The `<dst> <src> <len>` lines of a map, both ranges within `u64`.
+fn iter_map(map_ranges: Span) -> Result<Vec<Map>, Diagnostic> {
+    lines(map_ranges).map(|map_range| {
+                         let [dst, src, len] = tokens(map_range)?;
+                         let map = Map{ src: int(src)?, dst: int(dst)?, len: int(len)? };
+                         if !map.fits() {
+                             return Err(len.error("range goes past u64::MAX").with_hint("ids of both ranges should fit in a u64"));
+                         }
+                         Ok(map)
+                     })
+                     .collect()
+}
+
+fn iter_dst(map_iter: &[Map], src: IdRange) -> Vec<IdRange> {
+    trace!(?src, "find destinations");
+    let mut set = Box::new(Set::new(src));
+    let mut dsts: Vec<IdRange> = Vec::new();
+    map_iter.iter()
+            .filter_map(|map| map.contains(src))
+            .for_each(|(src, dst)| {
+                set.sub(src);
+                trace!(?set, "remained sources");
+                dsts.push(dst)
+            });
+
+    let unmapped = set.yield_intervals().collect();
+    trace!(?set, ?unmapped, "append unmapped");
+    push_iter(&mut dsts, unmapped);
+
+    if dsts.is_empty() {
+        vec![src]
+    } else {
+        dsts
+    }
+}
+
+fn vec_iter_dst(map_vec: &[Map], srcs: &[IdRange]) -> Vec<IdRange> {
+    let mut new_dsts = Vec::new();
+    for src in srcs {
+        push_iter(&mut new_dsts, iter_dst(map_vec, *src));
+    }
+    new_dsts
+}
+
+fn push_iter<T>(vec: &mut Vec<T>, concat_vec: Vec<T>) {
+    for elem in concat_vec {
+        vec.push(elem);
+    }
+}
+
+
+/// The almanac: the seeds line and the maps, in the order they appear.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "AlmanacFields")]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<(String, Vec<Map>)>,
+}
+
+/// An `Almanac` as serialized, checked like a parsed one when loaded.
+#[derive(Deserialize)]
+struct AlmanacFields {
+    seeds: Vec<u64>,
+    maps: Vec<(String, Vec<Map>)>,
+}
+
+impl TryFrom<AlmanacFields> for Almanac {
+    type Error = Error;
+
+    fn try_from(AlmanacFields { seeds, maps }: AlmanacFields) -> Result<Almanac> {
+        if seeds.is_empty() {
+            bail!("Expected at least one seed");
+        }
+        let names: Vec<&str> = maps.iter().map(|(name, _)| name.as_str()).collect();
+        if names != MAP_NAMES {
+            bail!("Expected the maps in order: {}, found: {}", MAP_NAMES.join(", "), names.join(", "));
+        }
+        if let Some((name, map)) = maps.iter().find_map(|(name, map)| Some((name, map.iter().find(|map| !map.fits())?))) {
+            bail!("Range of {} ids from {} or {} goes past u64::MAX in {}", map.len, map.src, map.dst, name);
+        }
+        Ok(Almanac { seeds, maps })
+    }
+}
+
+/// Parse the almanac, made of blank-line separated `<name>: <values>` blocks:
+/// the seeds, then each map of `MAP_NAMES` in order.
+#[aoc_parser(day = 5)]
+pub fn parse(input: &str) -> Result<Almanac> {
+    let mut seeds = None;
+    let mut maps = Vec::new();
+    for block in blocks(Span::new(input)) {
+        let (name, values) = label(block)?;
+        match name.as_str() {
+            "seeds" if seeds.is_some() => return Err(name.error("repeated `seeds:`").with_hint("the almanac lists its seeds once").into()),
+            "seeds" => {
+                let ids: Vec<u64> = ints(values)?;
+                if ids.is_empty() {
+                    return Err(name.error("expected at least one seed").with_hint("list the seed ids after `seeds:`").into());
+                }
+                seeds = Some(ids);
+            },
+            map_name if MAP_NAMES.get(maps.len()) == Some(&map_name) => maps.push((map_name.to_string(), iter_map(values)?)),
+            map_name => {
+                let error = match MAP_NAMES.get(maps.len()) {
+                    Some(expected) if MAP_NAMES.contains(&map_name) => name.error(format!("expected `{}:`, found `{}:`", expected, map_name)),
+                    Some(_) => name.error(format!("unsupported map name {:?}", map_name)),
+                    None => name.error(format!("unexpected block after `{}:`", MAP_NAMES[MAP_NAMES.len() - 1])),
+                };
+                return Err(error.with_hint(format!("expected `seeds` and the maps in order: {}", MAP_NAMES.join(", "))).into());
+            },
+        }
+    }
+    let end = Span::new(input).slice(input.len()..input.len());
+    let seeds = seeds.ok_or_else(|| Span::new(input).error("expected `seeds:`").with_hint("the almanac should list its seeds"))?;
+    if let Some(missing) = MAP_NAMES.get(maps.len()) {
+        return Err(end.error(format!("missing `{}:`", missing)).with_hint(format!("the almanac should have every map: {}", MAP_NAMES.join(", "))).into());
+    }
+    Ok(Almanac { seeds, maps })
+}
+
+/// The seed range of `len` ids from `start`, `None` when empty.
+fn seed(start: u64, len: u64) -> Result<Option<Seed>> {
+    match start.checked_add(len) {
+        Some(_) if len == 0 => Ok(None),
+        Some(_) => Ok(Some(Seed::new(start, len))),
+        None => Err(anyhow!("Seed range of {} ids from {} goes past u64::MAX", len, start)),
+    }
+}
+
+fn lowest_location(almanac: &Almanac, mut seeds: Vec<Seed>) -> Result<u64> {
+    for (name, map) in &almanac.maps {
+        let _span = debug_span!("map", name = name.as_str()).entered();
+        match name.as_str() {
+            "seed-to-soil map"            => seeds.iter_mut().for_each(|seed| push_iter(&mut seed.soil,       iter_dst(map, seed.seed))),
+            "soil-to-fertilizer map"      => seeds.iter_mut().for_each(|seed| push_iter(&mut seed.fertilizer, vec_iter_dst(map, &seed.soil))),
+            "fertilizer-to-water map"     => seeds.iter_mut().for_each(|seed| push_iter(&mut seed.water,      vec_iter_dst(map, &seed.fertilizer))),
+            "water-to-light map"          => seeds.iter_mut().for_each(|seed| push_iter(&mut seed.light,      vec_iter_dst(map, &seed.water))),
+            "light-to-temperature map"    => seeds.iter_mut().for_each(|seed| push_iter(&mut seed.temp,       vec_iter_dst(map, &seed.light))),
+            "temperature-to-humidity map" => seeds.iter_mut().for_each(|seed| push_iter(&mut seed.humidity,   vec_iter_dst(map, &seed.temp))),
+            "humidity-to-location map"    => seeds.iter_mut().for_each(|seed| push_iter(&mut seed.location,   vec_iter_dst(map, &seed.humidity))),
+            _ => bail!("Unsupported map name: {}", name),
+        }
+    }
+
+    debug!(?seeds, "mapped");
+    let mut locations: Vec<IdRange> = seeds.iter().flat_map(|seed| seed.location.iter()).cloned().collect();
+    locations.sort();
+    locations.first().map(|location| location.start).ok_or(anyhow!("No seed reaches a location"))
+}
+
+/// Lowest location of the seeds, each seed id taken on its own.
+#[aoc(day = 5, part = 1)]
+pub fn part1(almanac: &Almanac) -> Result<u64> {
+    let seeds = almanac.seeds.iter().filter_map(|seed_id| seed(*seed_id, 1).transpose()).collect::<Result<_>>()?;
+    lowest_location(almanac, seeds)
+}
+
+/// Lowest location of the seeds, read as `start len` range pairs.
+#[aoc(day = 5, part = 2)]
+pub fn part2(almanac: &Almanac) -> Result<u64> {
+    if !almanac.seeds.len().is_multiple_of(2) {
+        bail!("Expected seeds as `start length` pairs, found {} numbers", almanac.seeds.len());
+    }
+    let seeds = almanac.seeds.iter().tuples().filter_map(|(start, len)| seed(*start, *len).transpose()).collect::<Result<_>>()?;
+    lowest_location(almanac, seeds)
+}
@@ -0,0 +1,15 @@
+//! Checks the solver still finds the locked answers of the real `input`.
+
+use aoc_common::answers;
+// Link the day so its solvers get registered.
+use day_5 as _;
+
+#[test]
+fn part1_answer() {
+    answers::check(env!("CARGO_MANIFEST_DIR"), 5, 1);
+}
+
+#[test]
+fn part2_answer() {
+    answers::check(env!("CARGO_MANIFEST_DIR"), 5, 2);
+}
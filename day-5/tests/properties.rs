@@ -0,0 +1,65 @@
+//! Random almanacs checked against a reference solver that maps every seed
+//! id on its own instead of splitting ranges.
+
+use proptest::prelude::*;
+use day_5::{parse, part1, part2};
+
+const MAP_NAMES: [&str; 7] = ["seed-to-soil", "soil-to-fertilizer", "fertilizer-to-water", "water-to-light",
+                              "light-to-temperature", "temperature-to-humidity", "humidity-to-location"];
+
+/// Seeds as `start len` pairs, and per map its `(dst, src, len)` lines with
+/// disjoint source ranges.
+type Almanac = (Vec<(u64, u64)>, Vec<Vec<(u64, u64, u64)>>);
+
+fn almanac() -> impl Strategy<Value = Almanac> {
+    let seeds = prop::collection::vec((0..120_u64, 1..12_u64), 1..5);
+    let map = prop::collection::vec((0..120_u64, 0..10_u64, 1..15_u64), 1..6).prop_map(|lines| {
+        let mut src = 0;
+        lines.into_iter().map(|(dst, gap, len)| {
+            src += gap;
+            let line = (dst, src, len);
+            src += len;
+            line
+        }).collect()
+    });
+    (seeds, prop::collection::vec(map, 7))
+}
+
+fn render((seeds, maps): &Almanac) -> String {
+    let seeds: Vec<String> = seeds.iter().map(|(start, len)| format!("{} {}", start, len)).collect();
+    let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+    for (name, map) in MAP_NAMES.iter().zip(maps) {
+        almanac += &format!("\n{} map:\n", name);
+        for (dst, src, len) in map {
+            almanac += &format!("{} {} {}\n", dst, src, len);
+        }
+    }
+    almanac
+}
+
+fn location(maps: &[Vec<(u64, u64, u64)>], seed: u64) -> u64 {
+    maps.iter().fold(seed, |id, map| {
+        map.iter()
+           .find(|(_, src, len)| (*src..src + len).contains(&id))
+           .map(|(dst, src, _)| dst + id - src)
+           .unwrap_or(id)
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn part1_matches_reference(almanac in almanac()) {
+        let (seeds, maps) = &almanac;
+        let expected = seeds.iter().flat_map(|(start, len)| [*start, *len]).map(|seed| location(maps, seed)).min().unwrap();
+        prop_assert_eq!(part1(&parse(&render(&almanac)).unwrap()).unwrap(), expected);
+    }
+
+    #[test]
+    fn part2_matches_reference(almanac in almanac()) {
+        let (seeds, maps) = &almanac;
+        let expected = seeds.iter().flat_map(|(start, len)| *start..start + len).map(|seed| location(maps, seed)).min().unwrap();
+        prop_assert_eq!(part2(&parse(&render(&almanac)).unwrap()).unwrap(), expected);
+    }
+}
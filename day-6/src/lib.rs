@@ -0,0 +1,101 @@
+//! Day 6: Wait For It
+//!
+//! Count the ways to beat the record of each boat race, solving the
+//! quadratic `hold * (time - hold) > dist` directly.
+
+use anyhow::{anyhow, bail, Context, Error, Result};
+use tracing::debug;
+use serde::{Deserialize, Serialize};
+use aoc_common::{aoc, aoc_parser};
+use aoc_common::parse::{field, ints, lines, Span};
+
+/// The race table: the time and record distance of each race.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RacesFields")]
+pub struct Races {
+    pub times: Vec<u64>,
+    pub dists: Vec<u64>,
+}
+
+/// `Races` as serialized, checked like a parsed table when loaded.
+#[derive(Deserialize)]
+struct RacesFields {
+    times: Vec<u64>,
+    dists: Vec<u64>,
+}
+
+impl TryFrom<RacesFields> for Races {
+    type Error = Error;
+
+    fn try_from(RacesFields { times, dists }: RacesFields) -> Result<Races> {
+        if times.is_empty() {
+            bail!("Expected at least one race");
+        }
+        if times.len() != dists.len() {
+            bail!("{} times for {} distances, every race needs both", times.len(), dists.len());
+        }
+        Ok(Races { times, dists })
+    }
+}
+
+/// Read the numbers of `values` as the digits of a single number, ignoring
+/// the spaces between them.
+fn field_concat(values: &[u64]) -> Result<u64> {
+    let field_str = values.iter().fold(String::new(), |acc, x| acc + &x.to_string());
+    debug!(field = field_str, "concatenated");
+    field_str.parse().with_context(|| format!("Race of {} doesn't fit in a u64", field_str))
+}
+
+/// Number of ways to hold the button so the boat travels further than `dist`
+/// within `time`, none when the record can't be beaten.
+pub fn ways_to_win(time: u64, dist: u64) -> u64 {
+        let Some(delta)      = (time as u128 * time as u128).checked_sub(4 * dist as u128) else { return 0 };
+        let delta_sqrt       = delta.isqrt();
+        let exact            = delta == delta_sqrt*delta_sqrt;
+        let root_left_float  = ((time as f64) - (delta as f64).sqrt())/2.0;
+        let root_right_float = ((time as f64) + (delta as f64).sqrt())/2.0;
+        let root_left        = root_left_float.ceil() as u64;
+        let root_right       = root_right_float.floor() as u64;
+        // Holding exactly the time of a root only ties the record
+        let size = (root_right as u128 + 1).saturating_sub(root_left as u128);
+        let size = if exact { size.saturating_sub(2) } else { size };
+        u64::try_from(size).unwrap_or(u64::MAX)
+}
+
+/// Parse the `Time:` and `Distance:` lines of the race table.
+#[aoc_parser(day = 6)]
+pub fn parse(input: &str) -> Result<Races> {
+    let input = Span::new(input);
+    let mut rows = lines(input);
+    let (Some(times_row), Some(dists_row)) = (rows.next(), rows.next()) else {
+        return Err(input.error("expected `Time:` and `Distance:` lines").with_hint("the race table has two lines").into());
+    };
+    let times: Vec<u64> = ints(field(times_row, "Time")?)?;
+    let dists: Vec<u64> = ints(field(dists_row, "Distance")?)?;
+    if times.is_empty() {
+        return Err(times_row.error("expected at least one race").with_hint("list the time of each race after `Time:`").into());
+    }
+    if times.len() != dists.len() {
+        return Err(dists_row.error(format!("{} times for {} distances", times.len(), dists.len()))
+                            .with_hint("every race needs both a time and a distance").into());
+    }
+    Ok(Races { times, dists })
+}
+
+/// Product of the ways to win each race.
+#[aoc(day = 6, part = 1)]
+pub fn part1(races: &Races) -> Result<u64> {
+    let mut result: u64 = 1;
+    for (time, dist) in races.times.iter().zip(races.dists.iter()) {
+        result = result.checked_mul(ways_to_win(*time, *dist)).ok_or(anyhow!("Product of the ways to win overflows a u64"))?;
+    }
+    Ok(result)
+}
+
+/// Ways to win the single race written with bad kerning.
+#[aoc(day = 6, part = 2)]
+pub fn part2(races: &Races) -> Result<u64> {
+    let time = field_concat(&races.times)?;
+    let dist = field_concat(&races.dists)?;
+    Ok(ways_to_win(time, dist))
+}
@@ -1,73 +1,37 @@
-use log::debug;
-use env_logger;
-use std::fs;
-use anyhow::{anyhow, Result};
-use itertools::Itertools;
-
-fn field_vec(vec_str: &str) -> Vec<u64> {
-    vec_str.split(':')
-           .skip(1)
-           .next()
-           .unwrap()
-           .trim()
-           .split_whitespace()
-           .map(|x| x.parse().unwrap())
-           .collect()
-}
-
-fn field_concat(vec_str: &str) -> u64 {
-    let field_str = vec_str.split(':')
-                          .skip(1)
-                          .next()
-                          .unwrap()
-                          .trim()
-                          .split_whitespace()
-                          .fold(String::new(), |acc, x| acc+x);
-    debug!("Field: {}", field_str);
-    field_str.parse().unwrap()
-}
-
-fn ways_to_win(time: u64, dist: u64) -> u64 {
-        let delta            = time*time - 4*dist;
-        let delta_sqrt       = (delta as f64).sqrt() as u64;
-        let exact            = delta == delta_sqrt*delta_sqrt;
-        let root_left_float  = ((time as f64) - (delta as f64).sqrt())/2.0;
-        let root_right_float = ((time as f64) + (delta as f64).sqrt())/2.0;
-        let root_left        = root_left_float.ceil() as u64;
-        let root_right       = root_right_float.floor() as u64;
-        let mut size = root_right - root_left + 1;
-        if exact {
-            size = size - 2;
-        }
-        size
+use anyhow::Result;
+use clap::Parser;
+use aoc_common::trace::TraceArgs;
+use aoc_common::input::{self, InputArgs};
+use aoc_common::PartArgs;
+use day_6::{parse, part1, part2};
+
+/// Advent of Code 2023, day 6: Wait For It
+#[derive(Parser, Debug)]
+#[command(version)]
+struct Args {
+    #[command(flatten)]
+    part: PartArgs,
+    #[command(flatten)]
+    input: InputArgs,
+    #[command(flatten)]
+    trace: TraceArgs,
 }
 
 fn main() -> Result<()> {
-    env_logger::init();
-    let input: String = fs::read_to_string("input")?.parse()?;
-    let args: Vec<String> = std::env::args().collect();
-    let part: u32 = args[1].parse()?;
-    let lines: Vec<&str> = input.split('\n').filter(|line| !line.is_empty()).take(2).collect();
-    let mut result = 1;
-
-    match part {
-        1 => {
-            let times = field_vec(lines[0]);
-            let dists = field_vec(lines[1]);
-            for (time, dist) in times.iter().zip(dists.iter()) {
-                result *= ways_to_win(*time, *dist);
+    let args = Args::parse();
+    let _trace = args.trace.init()?;
+    let inputs = args.input.load(|| input::load(6))?;
+    for input in &inputs {
+        if inputs.len() > 1 {
+            println!("==> {} <==", input.name);
+        }
+        let races = input.parse(parse)?;
+        for part in args.part.parts() {
+            match part {
+                1 => println!("Part 1: {}", part1(&races)?),
+                _ => println!("Part 2: {}", part2(&races)?),
             }
-        },
-        2 => {
-            let time = field_concat(lines[0]);
-            let dist = field_concat(lines[1]);
-            result = ways_to_win(time, dist);
-
-        },
-        _ => panic!("Part {} no supported", part),
-    };
-
-    println!("Part {}: {}", part, result);
-
+        }
+    }
     Ok(())
 }
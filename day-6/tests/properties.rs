@@ -0,0 +1,43 @@
+//! Random race tables checked against a reference solver trying every
+//! button hold time.
+
+use proptest::prelude::*;
+use day_6::{parse, part1, part2};
+
+/// Races as `(time, record)`, every record being beatable.
+fn races() -> impl Strategy<Value = Vec<(u64, u64)>> {
+    let race = (1..100_u64).prop_flat_map(|time| (Just(time), 0..=(time * time - 1) / 4));
+    prop::collection::vec(race, 1..4)
+}
+
+fn render(races: &[(u64, u64)]) -> String {
+    let row = |values: Vec<u64>| values.iter().map(|value| format!("{:>5}", value)).collect::<String>();
+    format!("Time:     {}\nDistance: {}\n", row(races.iter().map(|race| race.0).collect()),
+                                             row(races.iter().map(|race| race.1).collect()))
+}
+
+fn ways(time: u64, record: u64) -> u64 {
+    (0..=time).filter(|hold| hold * (time - hold) > record).count() as u64
+}
+
+fn concat(values: impl Iterator<Item = u64>) -> u64 {
+    values.map(|value| value.to_string()).collect::<String>().parse().unwrap()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn part1_matches_reference(races in races()) {
+        let expected: u64 = races.iter().map(|(time, record)| ways(*time, *record)).product();
+        prop_assert_eq!(part1(&parse(&render(&races)).unwrap()).unwrap(), expected);
+    }
+
+    #[test]
+    fn part2_matches_reference(races in races()) {
+        let time = concat(races.iter().map(|race| race.0));
+        let record = concat(races.iter().map(|race| race.1));
+        prop_assume!(record < time * time / 4);
+        prop_assert_eq!(part2(&parse(&render(&races)).unwrap()).unwrap(), ways(time, record));
+    }
+}
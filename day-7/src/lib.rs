@@ -0,0 +1,386 @@
+//! Day 7: Camel Cards
+//!
+//! Rank hands of Camel Cards by category then card by card, with jokers
+//! turning into the most useful card in part two.
+
+use anyhow::{bail, Error, Result};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Write;
+use itertools::Itertools;
+use tracing::{debug, instrument, trace};
+use serde::{Deserialize, Serialize};
+use aoc_common::{aoc, aoc_parser};
+use aoc_common::parse::{int, lines, tokens, Span};
+use aoc_common::diagnostic::Diagnostic;
+
+/// Labels of the cards, from the weakest to the strongest.
+pub const LABELS: &str = "23456789TJQKA";
+
+/// A single card, labelled `2`..`9`, `T`, `J`, `Q`, `K` or `A`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CardType {
+    pub card: char,
+}
+
+impl CardType {
+    /// Strength of the card, jokers being the weakest card when `joker`.
+    pub fn into(&self, joker: bool) -> Result<u32> {
+        match self.card {
+            '2'..='9' => Ok(self.card.to_digit(10).unwrap()),
+            'T' => Ok(10),
+            'J' => Ok(if joker { 1 } else { 11 }),
+            'Q' => Ok(12),
+            'K' => Ok(13),
+            'A' => Ok(14),
+            _ => bail!("Unrecognized card type: {}", self.card),
+        }
+    }
+}
+
+/// Hand category described by its multiplicity signature: the sizes of the
+/// groups of equal cards, sorted in descending order (e.g. `[3, 2]` is a full
+/// house). Signatures compare lexicographically, so the category ordering
+/// extends naturally to hands of any size.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct HandType {
+    pub signature: Vec<usize>,
+}
+
+impl HandType {
+    pub fn new(cards: &[CardType], joker: bool) -> Self {
+        let counts = cards.iter()
+                          .filter(|card| !(joker && card.card == 'J'))
+                          .map(|card| card.card)
+                          .counts();
+        let jokers = cards.len() - counts.values().sum::<usize>();
+        let mut signature: Vec<usize> = counts.into_values().collect();
+        signature.sort();
+        signature.reverse();
+        trace!(?signature, "without jokers");
+        // Jokers always do best by joining the largest group
+        match signature.first_mut() {
+            Some(largest) => *largest += jokers,
+            None if jokers > 0 => signature.push(jokers),
+            None => {},
+        }
+        trace!(jokers, ?signature, "with jokers");
+        HandType { signature }
+    }
+
+    fn group_name(size: usize, cnt: usize) -> String {
+        let words = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten"];
+        let word = |n: usize| words.get(n).map(|w| w.to_string()).unwrap_or(n.to_string());
+        match (size, cnt) {
+            (2, 1) => "one pair".to_string(),
+            (2, _) => format!("{} pairs", word(cnt)),
+            (3, 1) => "three of a kind".to_string(),
+            (3, _) => format!("{} triples", word(cnt)),
+            (_, 1) => format!("{} of a kind", word(size)),
+            (_, _) => format!("{} {}s of a kind", word(cnt), word(size)),
+        }
+    }
+}
+
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.signature == [3, 2] {
+            return write!(f, "full house");
+        }
+        let groups: Vec<String> = self.signature.iter()
+                                      .filter(|size| **size > 1)
+                                      .dedup_with_count()
+                                      .map(|(cnt, size)| HandType::group_name(*size, cnt))
+                                      .collect();
+        if groups.is_empty() {
+            write!(f, "high card")
+        } else {
+            write!(f, "{}", groups.join(" and "))
+        }
+    }
+}
+
+/// A hand ready to be ranked: its category, the card its jokers stand for
+/// and the card strengths breaking ties between hands of the same category.
+#[derive(Clone, Debug, Serialize)]
+pub struct Hand {
+    pub hand: String,
+    pub hand_type: HandType,
+    pub substitute: Option<CardType>,
+    pub strength: Vec<u32>,
+    pub bid: u32,
+}
+
+impl Hand {
+    #[instrument(level = "debug", name = "hand", skip(bid))]
+    pub fn new(hand_str: &str, bid: u32, joker: bool) -> Result<Self> {
+        let cards = Hand::parse_hand(hand_str);
+        let strength = cards.iter().map(|x| x.into(joker)).collect::<Result<_>>()?;
+        let hand_type = HandType::new(&cards, joker);
+        let substitute = if joker { Hand::joker_substitute(&cards) } else { None };
+        Ok(Hand {
+            hand: hand_str.to_string(),
+            hand_type,
+            substitute,
+            strength,
+            bid,
+        })
+    }
+
+    /// Card the jokers impersonate: the most frequent other card, preferring
+    /// the strongest one on ties (aces when the hand is only jokers).
+    fn joker_substitute(cards: &[CardType]) -> Option<CardType> {
+        if !cards.iter().any(|card| card.card == 'J') {
+            return None;
+        }
+        let counts = cards.iter()
+                          .filter(|card| card.card != 'J')
+                          .map(|card| card.card)
+                          .counts();
+        let card = counts.into_iter()
+                         .max_by_key(|(card, cnt)| (*cnt, CardType::into(&CardType{card: *card}, true).ok()))
+                         .map(|(card, _)| card)
+                         .unwrap_or('A');
+        Some(CardType{card})
+    }
+
+    pub fn parse_hand(hand_str: &str) -> Vec<CardType> {
+        hand_str.chars()
+                .map(|card| CardType{card})
+                .collect::<Vec<_>>()
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.hand_type == other.hand_type && self.strength == other.strength
+    }
+}
+impl Eq for Hand {}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let ord = self.hand_type.cmp(&other.hand_type);
+        match ord
+        {
+            Ordering::Equal => self.strength.cmp(&other.strength),
+            _ => ord,
+        }
+    }
+}
+
+impl PartialOrd for Hand{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A hand as dealt, with its bid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "DealFields")]
+pub struct Deal {
+    pub hand: String,
+    pub bid: u32,
+}
+
+/// A `Deal` as serialized, checked like a parsed one when loaded.
+#[derive(Deserialize)]
+struct DealFields {
+    hand: String,
+    bid: u32,
+}
+
+impl TryFrom<DealFields> for Deal {
+    type Error = Error;
+
+    fn try_from(DealFields { hand, bid }: DealFields) -> Result<Deal> {
+        if hand.is_empty() {
+            bail!("Empty hand");
+        }
+        if let Some((_, card)) = unrecognized_card(&hand) {
+            bail!("Unrecognized card {:?} in hand {:?}, cards are one of {}", card, hand, LABELS);
+        }
+        Ok(Deal { hand, bid })
+    }
+}
+
+/// The first card of `hand` that isn't one of `LABELS`, with its offset.
+fn unrecognized_card(hand: &str) -> Option<(usize, char)> {
+    hand.char_indices().find(|&(_, card)| !LABELS.contains(card))
+}
+
+/// Parse the list of `<hand> <bid>` lines, hands being made of `LABELS`.
+#[aoc_parser(day = 7)]
+pub fn parse(input: &str) -> Result<Vec<Deal>> {
+    let deals = lines(Span::new(input)).map(|line| {
+                                           let [hand, bid] = tokens(line)?;
+                                           if let Some((at, card)) = unrecognized_card(hand.as_str()) {
+                                               return Err(hand.slice(at..at + card.len_utf8()).error(format!("unrecognized card {:?}", card))
+                                                                                                   .with_hint(format!("cards are one of {}", LABELS)));
+                                           }
+                                           Ok(Deal { hand: hand.as_str().to_string(), bid: int(bid)? })
+                                       })
+                                       .collect::<Result<_, Diagnostic>>()?;
+    Ok(deals)
+}
+
+/// Hands of `deals` sorted from the weakest to the strongest, with their
+/// index in `deals`.
+fn ranked(deals: &[Deal], joker: bool) -> Result<Vec<(usize, Hand)>> {
+    let mut hands = deals.iter().enumerate()
+                             .map(|(index, deal)| Ok((index, Hand::new(&deal.hand, deal.bid, joker)?)))
+                             .collect::<Result<Vec<_>>>()?;
+    hands.sort_by(|(_, a), (_, b)| a.cmp(b));
+    Ok(hands)
+}
+
+/// Hands of `deals` sorted from the weakest to the strongest.
+pub fn ranking(deals: &[Deal], joker: bool) -> Result<Vec<Hand>> {
+    Ok(ranked(deals, joker)?.into_iter().map(|(_, hand)| hand).collect())
+}
+
+fn winnings(rank: usize, hand: &Hand) -> u64 {
+    (rank as u64+1)*(hand.bid as u64)
+}
+
+fn total_winnings(hands: &[Hand]) -> u64 {
+    hands.iter().enumerate().map(|(rank, hand)| {
+        debug!(rank = rank + 1, hand_type = %hand.hand_type, ?hand, "ranked");
+        winnings(rank, hand)
+    }).sum::<u64>()
+}
+
+fn explain_ranking(out: &mut String, hands: &[(usize, Hand)], joker: bool) -> fmt::Result {
+    writeln!(out, "{:>5} {:<12} {:<28} {:<5} {:<24} {:>6} {:>10}", "rank", "hand", "type", "joker", "tie-break", "bid", "winnings")?;
+    for (rank, (_, hand)) in hands.iter().enumerate() {
+        let substitute = match (joker, hand.substitute) {
+            (true, Some(card)) => format!("J={}", card.card),
+            _ => "-".to_string(),
+        };
+        writeln!(out, "{:>5} {:<12} {:<28} {:<5} {:<24} {:>6} {:>10}",
+                 rank+1, hand.hand, hand.hand_type.to_string(), substitute,
+                 format!("{:?}", hand.strength), hand.bid, winnings(rank, hand))?;
+    }
+    Ok(())
+}
+
+/// Report of both rankings with the details behind each rank, followed by
+/// how every hand moved from part one to part two.
+pub fn explain(deals: &[Deal]) -> Result<String> {
+    let mut out = String::new();
+    let part1 = ranked(deals, false)?;
+    let part2 = ranked(deals, true)?;
+    for (part, hands) in [(1, &part1), (2, &part2)] {
+        writeln!(out, "Part {} ranking:", part)?;
+        explain_ranking(&mut out, hands, part==2)?;
+        writeln!(out)?;
+    }
+
+    writeln!(out, "Ranking diff (part 1 -> part 2):")?;
+    writeln!(out, "{:<12} {:>5} {:>5} {:>6}  type", "hand", "p1", "p2", "delta")?;
+    // Keyed by position in the deal: equal hands are distinct deals.
+    let mut ranks1 = vec![0; deals.len()];
+    for (rank, (index, _)) in part1.iter().enumerate() {
+        ranks1[*index] = rank+1;
+    }
+    for (rank2, (index, hand2)) in part2.iter().enumerate().map(|(rank, hand)| (rank+1, hand)) {
+        let rank1 = ranks1[*index];
+        let type1 = &part1[rank1-1].1.hand_type;
+        let type_change = if *type1 == hand2.hand_type {
+            type1.to_string()
+        } else {
+            format!("{} -> {}", type1, hand2.hand_type)
+        };
+        writeln!(out, "{:<12} {:>5} {:>5} {:>+6}  {}", hand2.hand, rank1, rank2, rank2 as i64 - rank1 as i64, type_change)?;
+    }
+    Ok(out)
+}
+
+/// Total winnings with `J` as jacks.
+#[aoc(day = 7, part = 1)]
+pub fn part1(deals: &[Deal]) -> Result<u64> {
+    Ok(total_winnings(&ranking(deals, false)?))
+}
+
+/// Total winnings with `J` as jokers.
+#[aoc(day = 7, part = 2)]
+pub fn part2(deals: &[Deal]) -> Result<u64> {
+    Ok(total_winnings(&ranking(deals, true)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A hand with a group of `size` equal cards per entry of `groups`,
+    /// never using `J`.
+    fn hand(groups: &[usize]) -> String {
+        groups.iter().zip(LABELS.chars().filter(|card| *card != 'J'))
+              .flat_map(|(size, card)| std::iter::repeat_n(card, *size))
+              .collect()
+    }
+
+    fn hand_type(hand: &str, joker: bool) -> HandType {
+        HandType::new(&Hand::parse_hand(hand), joker)
+    }
+
+    /// Every way to split `n` cards into groups, largest group first.
+    fn signatures(n: usize, largest: usize) -> Vec<Vec<usize>> {
+        if n == 0 {
+            return vec![vec![]];
+        }
+        (1..=largest.min(n)).flat_map(|size| signatures(n - size, size).into_iter().map(move |mut rest| {
+                                  rest.insert(0, size);
+                                  rest
+                              }))
+                            .collect()
+    }
+
+    #[test]
+    fn ranks_signatures_of_any_hand_size() {
+        for n in 3..=10 {
+            let mut expected = signatures(n, n);
+            expected.sort();
+            let mut ranked: Vec<HandType> = expected.iter().map(|groups| hand_type(&hand(groups), false)).collect();
+            ranked.sort();
+            let ranked: Vec<Vec<usize>> = ranked.into_iter().map(|hand_type| hand_type.signature).collect();
+            assert_eq!(ranked, expected, "{} cards", n);
+            assert_eq!((ranked[0].len(), &ranked[ranked.len() - 1]), (n, &vec![n]));
+        }
+        // A larger group beats any number of smaller ones
+        assert!(hand_type(&hand(&[3, 1, 1, 1, 1, 1, 1, 1]), false) > hand_type(&hand(&[2, 2, 2, 2, 2]), false));
+        assert!(hand_type(&hand(&[4, 1, 1]), false) > hand_type(&hand(&[3, 3]), false));
+        assert!(hand_type(&hand(&[3, 3]), false) > hand_type(&hand(&[3, 2, 1]), false));
+    }
+
+    #[test]
+    fn names_extended_categories() {
+        let name = |groups: &[usize]| hand_type(&hand(groups), false).to_string();
+        assert_eq!(name(&[6]), "six of a kind");
+        assert_eq!(name(&[10]), "ten of a kind");
+        assert_eq!(name(&[11]), "11 of a kind");
+        assert_eq!(name(&[3, 3]), "two triples");
+        assert_eq!(name(&[2, 2, 2]), "three pairs");
+        assert_eq!(name(&[4, 2]), "four of a kind and one pair");
+        assert_eq!(name(&[4, 4, 1]), "two fours of a kind");
+        assert_eq!(name(&[3, 3, 2, 2]), "two triples and two pairs");
+        assert_eq!(name(&[3, 2]), "full house");
+        assert_eq!(name(&[3, 2, 1]), "three of a kind and one pair");
+        assert_eq!(name(&[1, 1, 1]), "high card");
+    }
+
+    #[test]
+    fn jokers_join_the_largest_group_of_any_hand_size() {
+        let joker = |hand: &str| hand_type(hand, true);
+        assert_eq!(joker("JJ2").signature, [3]);
+        assert_eq!(joker("JJ23").signature, [3, 1]);
+        assert_eq!(joker("JJJJJJ").signature, [6]);
+        assert_eq!(joker("J23456").signature, [2, 1, 1, 1, 1]);
+        assert_eq!(joker("JJ222334").signature, [5, 2, 1]);
+        assert_eq!(joker("JJ222334").to_string(), "five of a kind and one pair");
+        assert_eq!(hand_type("JJ222334", false).signature, [3, 2, 2, 1]);
+        let hand = Hand::new("JJ222334", 1, true).unwrap();
+        assert_eq!(hand.substitute.map(|card| card.card), Some('2'));
+        assert_eq!(hand.strength, [1, 1, 2, 2, 2, 3, 3, 4]);
+    }
+}
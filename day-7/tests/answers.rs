@@ -0,0 +1,17 @@
+//! Checks the solver still finds the locked answers of the real `input`.
+
+use aoc_common::answers;
+// Link the day so its solvers get registered.
+use day_7 as _;
+
+#[test]
+#[ignore = "no input committed for day 7"]
+fn part1_answer() {
+    answers::check(env!("CARGO_MANIFEST_DIR"), 7, 1);
+}
+
+#[test]
+#[ignore = "no input committed for day 7"]
+fn part2_answer() {
+    answers::check(env!("CARGO_MANIFEST_DIR"), 7, 2);
+}
@@ -0,0 +1,16 @@
+//! The `--explain` report.
+
+use day_7::{explain, parse};
+
+#[test]
+fn ranks_duplicate_hands_separately() {
+    let deals = parse("KK677 10\nJJJJ2 5\nKK677 20\n").unwrap();
+    let report = explain(&deals).unwrap();
+    let diff: Vec<&str> = report.lines().skip_while(|line| !line.starts_with("Ranking diff")).skip(2).collect();
+    let ranks: Vec<(&str, &str, &str)> = diff.iter().map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        (fields[0], fields[1], fields[2])
+    }).collect();
+    assert_eq!(ranks, [("KK677", "1", "1"), ("KK677", "2", "2"), ("JJJJ2", "3", "3")]);
+    assert!(report.contains(&format!("{:>5} {:<12}", 2, "KK677")), "{}", report);
+}
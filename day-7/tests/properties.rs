@@ -0,0 +1,61 @@
+//! Random Camel Cards games checked against a reference solver using the
+//! seven classic hand categories and trying every card for the jokers.
+
+use proptest::prelude::*;
+use day_7::{parse, part1, part2, LABELS};
+
+/// Distinct hands of five cards with their bids.
+fn deals() -> impl Strategy<Value = Vec<(String, u32)>> {
+    let hand = prop::collection::vec(prop::sample::select(LABELS.chars().collect::<Vec<_>>()), 5)
+        .prop_map(|cards| cards.into_iter().collect::<String>());
+    prop::collection::btree_map(hand, 1..1000_u32, 1..40).prop_map(|deals| deals.into_iter().collect())
+}
+
+fn render(deals: &[(String, u32)]) -> String {
+    deals.iter().map(|(hand, bid)| format!("{} {}\n", hand, bid)).collect()
+}
+
+/// Five of a kind is 6, down to high card 0.
+fn category(hand: &str) -> u32 {
+    let mut counts: Vec<usize> = LABELS.chars().map(|label| hand.matches(label).count()).filter(|cnt| *cnt > 0).collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    match counts.as_slice() {
+        [5] => 6,
+        [4, ..] => 5,
+        [3, 2] => 4,
+        [3, ..] => 3,
+        [2, 2, ..] => 2,
+        [2, ..] => 1,
+        _ => 0,
+    }
+}
+
+fn winnings(deals: &[(String, u32)], joker: bool) -> u64 {
+    let strength = |card: char| match (card, joker) {
+        ('J', true) => 0,
+        _ => LABELS.find(card).unwrap() + 1,
+    };
+    let mut ranked: Vec<_> = deals.iter().map(|(hand, bid)| {
+        let category = match joker {
+            true => LABELS.chars().map(|label| category(&hand.replace('J', &label.to_string()))).max().unwrap(),
+            false => category(hand),
+        };
+        ((category, hand.chars().map(strength).collect::<Vec<_>>()), *bid)
+    }).collect();
+    ranked.sort();
+    ranked.iter().enumerate().map(|(rank, (_, bid))| (rank as u64 + 1) * *bid as u64).sum()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn part1_matches_reference(deals in deals()) {
+        prop_assert_eq!(part1(&parse(&render(&deals)).unwrap()).unwrap(), winnings(&deals, false));
+    }
+
+    #[test]
+    fn part2_matches_reference(deals in deals()) {
+        prop_assert_eq!(part2(&parse(&render(&deals)).unwrap()).unwrap(), winnings(&deals, true));
+    }
+}
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Malformed input must be an error, never a panic, and so must solving
+// whatever the parser accepts.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(model) = day_2::parse(input) {
+            let _ = day_2::part1(&model);
+            let _ = day_2::part2(&model);
+        }
+    }
+});